@@ -0,0 +1,218 @@
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    process,
+    slice::Iter,
+};
+
+use peptide_core::{
+    agent::{Agent, Annealing, Greedy},
+    anneal::{EXHAUSTIVE_RESIDUES, Schedule},
+    bench,
+    generator::GeneratorConfig,
+    level::{self, LEVELS, Level, MANIFEST, WIN_THRESHOLD},
+    protocol::Session,
+};
+use rand::{SeedableRng, rngs::StdRng};
+
+const USAGE: &str = "\
+usage: peptide <command>
+
+commands:
+  solve <level.ron>                           print the energy range and optimal fold,
+                                              approximated for big levels
+  generate --seed <n> [--difficulty <n>] [out.ron]
+                                              generate a random level
+  score <level.ron> <fold>                    break down the energy of a fold
+  validate <dir>                              check every level in a directory
+  bench <greedy|anneal> [--random <n>] [--seed <n>] [--difficulty <n>]
+                                              run a folding agent over the campaign
+                                              and <n> random levels
+  serve [level.ron]                           play over JSON lines on stdin and stdout,
+                                              starting on the first campaign level";
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+
+    let result = match &args[..] {
+        ["solve", level] => solve(Path::new(level)),
+        ["generate", args @ ..] => generate(args),
+        ["score", level, fold] => score(Path::new(level), fold),
+        ["validate", dir] => validate(Path::new(dir)),
+        ["bench", agent, args @ ..] => benchmark(agent, args),
+        ["serve"] => serve(None),
+        ["serve", level] => serve(Some(Path::new(level))),
+        _ => Err(USAGE.into()),
+    };
+
+    if let Err(error) = result {
+        eprintln!("{error}");
+        process::exit(1);
+    }
+}
+
+fn solve(path: &Path) -> Result<(), String> {
+    let level = load(path)?;
+    println!("{}", level.title);
+
+    // too many folds to try them all, so anneal for a good one instead
+    let optimal = if level.peptide.inner.len() > EXHAUSTIVE_RESIDUES {
+        let mut rng = StdRng::seed_from_u64(0);
+        let (optimal, energy) = level.anneal(&Schedule::default(), &mut rng);
+        println!("range: {:.2} to {:.2}", level.range.0, level.range.1);
+        println!("approximate optimum: {energy:.2}");
+        optimal
+    } else {
+        let (range, optimal) = level.solution();
+        println!("range: {:.2} to {:.2}", range.0, range.1);
+        optimal
+    };
+
+    println!("optimal: {}\n", optimal.to_fold());
+    println!("{optimal}");
+    Ok(())
+}
+
+fn generate(args: &[&str]) -> Result<(), String> {
+    let (mut seed, mut difficulty, mut out) = (None, None, None);
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match *arg {
+            "--seed" => seed = Some(number(&mut args, "--seed")?),
+            "--difficulty" => difficulty = Some(number(&mut args, "--difficulty")? as u32),
+            path if out.is_none() && !path.starts_with("--") => out = Some(PathBuf::from(path)),
+            _ => return Err(USAGE.into()),
+        }
+    }
+
+    let seed = seed.ok_or("generate needs a --seed")?;
+    // same as the game, where the seed picks the difficulty
+    let config = difficulty.map_or_else(
+        || GeneratorConfig::for_seed(seed),
+        GeneratorConfig::difficulty,
+    );
+    let level = Level::generate_reporting(&config, seed, |seed, rejections| {
+        let reasons = (rejections.iter()).map(ToString::to_string);
+        let reasons = reasons.collect::<Vec<_>>();
+        eprintln!("skipped seed {seed}: {}", reasons.join(", "));
+    })
+    .map_err(|x| x.to_string())?;
+    let level = level.to_ron();
+
+    match out {
+        Some(path) => fs::write(&path, level).map_err(|x| format!("{}: {x}", path.display())),
+        None => {
+            print!("{level}");
+            Ok(())
+        }
+    }
+}
+
+fn score(path: &Path, fold: &str) -> Result<(), String> {
+    let level = load(path)?;
+    let peptide = level.decode_fold(fold).map_err(|x| x.to_string())?;
+    let energy = peptide.energy();
+
+    println!("energy: {:.2}", energy.total());
+    println!("  intrinsic: {:.2}", energy.intrinsic);
+    println!("  adjacency: {:.2}", energy.adjacency);
+    println!("  hydrophobic: {:.2}", energy.hydrophobic);
+    println!("  electrostatic: {:.2}", energy.electrostatic);
+
+    let (built, total) = (peptide.inner.len(), level.peptide.inner.len());
+    if built < total {
+        println!("incomplete: {built} of {total} residues");
+    } else {
+        let score = level.score(energy.total());
+        let verdict = if score >= WIN_THRESHOLD {
+            "win"
+        } else {
+            "not a win"
+        };
+        println!("score: {:.0}% ({verdict})", score * 100.0);
+    }
+
+    Ok(())
+}
+
+fn validate(dir: &Path) -> Result<(), String> {
+    let entries = fs::read_dir(dir).map_err(|x| format!("{}: {x}", dir.display()))?;
+    let mut files = (entries.filter_map(Result::ok))
+        .map(|x| x.path())
+        .filter(|x| x.extension().is_some_and(|x| x == "ron"))
+        .filter(|x| x.file_name().is_some_and(|x| x != MANIFEST))
+        .collect::<Vec<_>>();
+    files.sort();
+
+    let mut problems = 0;
+    for path in &files {
+        let file = path.file_name().unwrap().to_string_lossy();
+        let raw = fs::read(path).map_err(|x| format!("{}: {x}", path.display()))?;
+        match level::verify_level(&file, &raw) {
+            Some(problem) => {
+                eprintln!("{problem}");
+                problems += 1;
+            }
+            None => println!("ok {file}"),
+        }
+    }
+
+    match problems {
+        0 => Ok(()),
+        _ => Err(format!(
+            "{problems} of {} levels have problems",
+            files.len()
+        )),
+    }
+}
+
+fn benchmark(agent: &str, args: &[&str]) -> Result<(), String> {
+    let (mut random, mut seed, mut difficulty) = (0, 0, None);
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match *arg {
+            "--random" => random = number(&mut args, "--random")?,
+            "--seed" => seed = number(&mut args, "--seed")?,
+            "--difficulty" => difficulty = Some(number(&mut args, "--difficulty")? as u32),
+            _ => return Err(USAGE.into()),
+        }
+    }
+
+    let mut agent: Box<dyn Agent> = match agent {
+        "greedy" => Box::new(Greedy),
+        "anneal" => Box::new(Annealing::new(Schedule::default(), seed)),
+        _ => return Err(format!("unknown agent '{agent}'")),
+    };
+
+    let config = difficulty.map_or_else(
+        || GeneratorConfig::for_seed(seed),
+        GeneratorConfig::difficulty,
+    );
+    let report = bench::benchmark(agent.as_mut(), &config, random, seed);
+    println!("{}", report.map_err(|x| x.to_string())?);
+    Ok(())
+}
+
+fn serve(path: Option<&Path>) -> Result<(), String> {
+    let level = match path {
+        Some(path) => load(path)?,
+        None => LEVELS[0].clone().map_err(|x| x.to_string())?,
+    };
+
+    let input = io::stdin().lock();
+    let mut session = Session::new(level);
+    (session.serve(input, io::stdout().lock())).map_err(|x| x.to_string())
+}
+
+// the value following a flag
+fn number(args: &mut Iter<&str>, name: &str) -> Result<u64, String> {
+    let value = args.next().ok_or(format!("missing value for {name}"))?;
+    (value.parse()).map_err(|_| format!("{name} must be a number, not '{value}'"))
+}
+
+fn load(path: &Path) -> Result<Level, String> {
+    let raw = fs::read(path).map_err(|x| format!("{}: {x}", path.display()))?;
+    let file = path.file_name().unwrap_or_default().to_string_lossy();
+    Level::load(&file, &raw).map_err(|x| x.to_string())
+}
@@ -0,0 +1,181 @@
+use std::{
+    collections::VecDeque,
+    fmt::{self, Display},
+};
+
+use nalgebra::Vector2;
+use rand::{SeedableRng, rngs::StdRng};
+
+use crate::{
+    amino::{Amino, AminoType},
+    anneal::Schedule,
+    direction::{Direction, Directions},
+    level::Level,
+    peptide::Peptide,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Place {
+        parent: Vector2<i32>,
+        dir: Direction,
+        amino: AminoType,
+    },
+    // removing the root clears the whole peptide
+    Remove(Vector2<i32>),
+}
+
+#[derive(Debug, Clone)]
+pub enum ActionError {
+    NoResidue(Vector2<i32>),
+    Occupied(Vector2<i32>),
+    Unavailable(AminoType),
+}
+
+pub trait Agent {
+    fn name(&self) -> &str;
+
+    fn start(&mut self, _level: &Level) {}
+
+    // None once the agent is done with the level
+    fn act(&mut self, level: &Level, peptide: &Peptide) -> Option<Action>;
+}
+
+impl Level {
+    pub fn can_place(&self, peptide: &Peptide, parent: Vector2<i32>, amino: AminoType) -> bool {
+        (0..3).any(|child_idx| {
+            (self.next_child(peptide, parent, child_idx))
+                .is_some_and(|pos| self.get(pos).unwrap().amino == amino)
+        })
+    }
+
+    pub fn placements(&self, peptide: &Peptide) -> Vec<Action> {
+        let mut out = Vec::new();
+        for parent in peptide.tree_order() {
+            for dir in Direction::ALL {
+                if peptide.inner.contains_key(&(parent + dir.delta())) {
+                    continue;
+                }
+
+                for amino in AminoType::ALL {
+                    if self.can_place(peptide, parent, amino) {
+                        out.push(Action::Place { parent, dir, amino });
+                    }
+                }
+            }
+        }
+
+        out
+    }
+
+    pub fn check(&self, peptide: &Peptide, action: Action) -> Result<(), ActionError> {
+        match action {
+            Action::Place { parent, dir, amino } => {
+                if peptide.get(parent).is_none() {
+                    return Err(ActionError::NoResidue(parent));
+                }
+
+                let pos = parent + dir.delta();
+                if peptide.inner.contains_key(&pos) {
+                    return Err(ActionError::Occupied(pos));
+                }
+
+                if !self.can_place(peptide, parent, amino) {
+                    return Err(ActionError::Unavailable(amino));
+                }
+            }
+            Action::Remove(pos) => {
+                if peptide.get(pos).is_none() {
+                    return Err(ActionError::NoResidue(pos));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn apply(&self, peptide: &mut Peptide, action: Action) -> Result<(), ActionError> {
+        self.check(peptide, action)?;
+        match action {
+            Action::Place { parent, dir, amino } => {
+                (peptide.inner.get_mut(&parent).unwrap().children).set(dir);
+                let children = Directions::empty();
+                peptide
+                    .inner
+                    .insert(parent + dir.delta(), Amino { amino, children });
+            }
+            Action::Remove(pos) => peptide.remove(pos),
+        }
+
+        Ok(())
+    }
+}
+
+pub struct Greedy;
+
+impl Agent for Greedy {
+    fn name(&self) -> &str {
+        "greedy"
+    }
+
+    fn act(&mut self, level: &Level, peptide: &Peptide) -> Option<Action> {
+        let score = |action: &Action| {
+            let mut peptide = peptide.clone();
+            level.apply(&mut peptide, *action).unwrap();
+            peptide.score()
+        };
+
+        (level.placements(peptide).into_iter())
+            .map(|x| (score(&x), x))
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|x| x.1)
+    }
+}
+
+pub struct Annealing {
+    pub schedule: Schedule,
+    rng: StdRng,
+    plan: VecDeque<Action>,
+}
+
+impl Annealing {
+    pub fn new(schedule: Schedule, seed: u64) -> Self {
+        Self {
+            schedule,
+            rng: StdRng::seed_from_u64(seed),
+            plan: VecDeque::new(),
+        }
+    }
+}
+
+impl Agent for Annealing {
+    fn name(&self) -> &str {
+        "anneal"
+    }
+
+    fn start(&mut self, level: &Level) {
+        let (fold, _) = level.anneal(&self.schedule, &mut self.rng);
+        self.plan = (fold.tree_order().into_iter())
+            .filter_map(|pos| {
+                let (parent, dir) = fold.parent(pos)?;
+                let amino = fold.get(pos).unwrap().amino;
+                let dir = dir.opposite();
+                Some(Action::Place { parent, dir, amino })
+            })
+            .collect();
+    }
+
+    fn act(&mut self, _level: &Level, _peptide: &Peptide) -> Option<Action> {
+        self.plan.pop_front()
+    }
+}
+
+impl Display for ActionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ActionError::NoResidue(pos) => write!(f, "no residue at ({}, {})", pos.x, pos.y),
+            ActionError::Occupied(pos) => write!(f, "({}, {}) is already taken", pos.x, pos.y),
+            ActionError::Unavailable(amino) => write!(f, "no {amino:?} left to place there"),
+        }
+    }
+}
@@ -1,19 +1,18 @@
-use engine::assets::SpriteRef;
-use serde::{Deserialize, Deserializer};
-
-use crate::{
-    assets,
-    misc::direction::{Direction, Directions},
+use serde::{
+    Deserialize, Deserializer, Serialize,
+    de::{Error, Unexpected},
 };
 
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Deserialize)]
+use crate::direction::{Direction, Directions};
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Amino {
     pub amino: AminoType,
     #[serde(deserialize_with = "parse_directions")]
     pub children: Directions,
 }
 
-#[derive(Debug, Hash, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Hash, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum AminoType {
     Ala, // A
     Cys, // C
@@ -23,6 +22,14 @@ pub enum AminoType {
     Arg, // R
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interaction {
+    SaltBridge,
+    Disulfide,
+    AromaticStacking,
+    Hydrophobic,
+}
+
 impl AminoType {
     pub const ALL: [AminoType; 6] = [
         AminoType::Ala,
@@ -44,6 +51,10 @@ impl AminoType {
         }
     }
 
+    pub fn from_letter(letter: char) -> Option<Self> {
+        Self::ALL.into_iter().find(|x| x.letter() == letter)
+    }
+
     pub fn name(&self) -> &str {
         match self {
             AminoType::Arg => "Arginine",
@@ -111,17 +122,6 @@ impl AminoType {
         out
     }
 
-    pub fn asset(&self) -> SpriteRef {
-        match self {
-            AminoType::Ala => assets::AMINO_A,
-            AminoType::Cys => assets::AMINO_C,
-            AminoType::Asp => assets::AMINO_D,
-            AminoType::Phe => assets::AMINO_F,
-            AminoType::Leu => assets::AMINO_L,
-            AminoType::Arg => assets::AMINO_R,
-        }
-    }
-
     pub fn intrinsic_cost(&self) -> i32 {
         match self {
             AminoType::Ala => 1,
@@ -195,6 +195,20 @@ impl AminoType {
     }
 }
 
+impl Interaction {
+    pub fn between(a: AminoType, b: AminoType) -> Option<Self> {
+        a.adjacency().iter().find(|x| x.0 == b)?;
+        Some(match (a, b) {
+            (AminoType::Arg, AminoType::Asp) | (AminoType::Asp, AminoType::Arg) => {
+                Interaction::SaltBridge
+            }
+            (AminoType::Cys, AminoType::Cys) => Interaction::Disulfide,
+            (AminoType::Phe, AminoType::Phe) => Interaction::AromaticStacking,
+            _ => Interaction::Hydrophobic,
+        })
+    }
+}
+
 pub fn parse_directions<'de, D>(from: D) -> Result<Directions, D::Error>
 where
     D: Deserializer<'de>,
@@ -209,7 +223,10 @@ where
                 'R' => Direction::Right,
                 'U' => Direction::Up,
                 'D' => Direction::Down,
-                _ => panic!(),
+                _ => {
+                    let expected = &"one of L, R, U or D";
+                    return Err(D::Error::invalid_value(Unexpected::Char(chr), expected));
+                }
             };
     }
 
@@ -0,0 +1,97 @@
+use std::collections::{BTreeMap, HashSet};
+
+use crate::{
+    amino::AminoType,
+    direction::Directions,
+    level::{Level, WIN_THRESHOLD, normalize},
+    peptide::Peptide,
+};
+
+// energies closer than this are considered the same
+const EPSILON: f32 = 1e-3;
+
+pub struct Analysis {
+    pub range: (f32, f32),
+    pub optimum: Peptide,
+
+    pub complete: usize,
+    // counting rotations and reflections of the same fold only once
+    pub optimal: usize,
+    pub winning: usize,
+    pub histogram: Vec<(f32, usize)>,
+}
+
+impl Analysis {
+    pub fn win_fraction(&self) -> f32 {
+        self.winning as f32 / self.complete.max(1) as f32
+    }
+}
+
+impl Level {
+    // None if the level has no complete fold at all
+    pub fn analyze(&self) -> Option<Analysis> {
+        let mut max = f32::MIN;
+        let mut energies = Vec::new();
+        let mut optimal = (f32::MAX, Vec::new());
+
+        self.explore(|peptide, score| {
+            max = max.max(score);
+            if peptide.inner.len() != self.peptide.inner.len() {
+                return;
+            }
+
+            energies.push(score);
+            if score < optimal.0 - EPSILON {
+                optimal = (score, vec![peptide.clone()]);
+            } else if score < optimal.0 + EPSILON {
+                optimal.0 = optimal.0.min(score);
+                optimal.1.push(peptide.clone());
+            }
+        });
+
+        if energies.is_empty() {
+            return None;
+        }
+
+        // scored against the fresh range rather than the stored one
+        let range = (optimal.0, max);
+        let winning = (energies.iter())
+            .filter(|x| normalize(range, **x) >= WIN_THRESHOLD)
+            .count();
+
+        let mut histogram = BTreeMap::<i64, (f32, usize)>::new();
+        for energy in &energies {
+            let bucket = (energy / EPSILON).round() as i64;
+            let entry = histogram.entry(bucket).or_insert((*energy, 0));
+            entry.1 += 1;
+        }
+
+        let symmetric = (optimal.1.iter()).map(canonical).collect::<HashSet<_>>();
+        let optimum = optimal.1.swap_remove(0);
+
+        Some(Analysis {
+            range,
+            optimum,
+
+            complete: energies.len(),
+            optimal: symmetric.len(),
+            winning,
+            histogram: histogram.into_values().collect(),
+        })
+    }
+}
+
+// smallest layout out of all the symmetries of a peptide
+fn canonical(peptide: &Peptide) -> Vec<(i32, i32, AminoType, Directions)> {
+    (0..8)
+        .map(|symmetry| {
+            let peptide = peptide.transformed(symmetry);
+            let mut items = (peptide.inner.iter())
+                .map(|(pos, amino)| (pos.x, pos.y, amino.amino, amino.children))
+                .collect::<Vec<_>>();
+            items.sort();
+            items
+        })
+        .min()
+        .unwrap()
+}
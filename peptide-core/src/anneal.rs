@@ -0,0 +1,361 @@
+use std::collections::HashMap;
+
+use nalgebra::Vector2;
+use rand::{Rng, seq::IndexedRandom};
+
+use crate::{
+    amino::{Amino, AminoType},
+    direction::{Direction, Directions},
+    level::Level,
+    peptide::Peptide,
+    transform,
+};
+
+pub const EXHAUSTIVE_RESIDUES: usize = 12;
+
+#[derive(Clone, Copy)]
+pub struct Schedule {
+    pub start: f32,
+    pub end: f32,
+    pub steps: u32,
+}
+
+// the residue tree is fixed, only the lattice positions change, and the
+// root always stays at the origin
+pub struct Annealer {
+    nodes: Vec<Node>,
+    positions: Vec<Vector2<i32>>,
+    occupied: HashMap<Vector2<i32>, usize>,
+    energy: f32,
+}
+
+struct Node {
+    amino: AminoType,
+    parent: Option<usize>,
+    children: Vec<usize>,
+}
+
+const MOVES: [Move; 6] = [
+    Move::End,
+    Move::Corner,
+    Move::Crankshaft,
+    Move::Pull,
+    Move::Pivot,
+    Move::Swap,
+];
+
+#[derive(Clone, Copy)]
+enum Move {
+    End,
+    Corner,
+    Crankshaft,
+    Pull,
+    Pivot,
+    Swap,
+}
+
+impl Schedule {
+    pub fn temperature(&self, step: u32) -> f32 {
+        let t = step as f32 / self.steps.max(1) as f32;
+        self.start * (self.end / self.start).powf(t)
+    }
+}
+
+impl Default for Schedule {
+    fn default() -> Self {
+        Self {
+            start: 10.0,
+            end: 0.05,
+            steps: 20_000,
+        }
+    }
+}
+
+impl Annealer {
+    pub fn new(peptide: &Peptide) -> Self {
+        let mut nodes = Vec::<Node>::new();
+        let mut positions = Vec::new();
+
+        let mut stack = vec![(Vector2::zeros(), None::<usize>)];
+        while let Some((pos, parent)) = stack.pop() {
+            let amino = peptide.get(pos).unwrap();
+            let idx = nodes.len();
+            if let Some(parent) = parent {
+                nodes[parent].children.push(idx);
+            }
+
+            nodes.push(Node {
+                amino: amino.amino,
+                parent,
+                children: Vec::new(),
+            });
+            positions.push(pos);
+
+            for dir in amino.children.iter() {
+                stack.push((pos + dir.delta(), Some(idx)));
+            }
+        }
+
+        let occupied = (positions.iter().enumerate())
+            .map(|(idx, pos)| (*pos, idx))
+            .collect();
+        let mut this = Self {
+            nodes,
+            positions,
+            occupied,
+            energy: 0.0,
+        };
+        this.energy = this.peptide().score();
+        this
+    }
+
+    pub fn energy(&self) -> f32 {
+        self.energy
+    }
+
+    pub fn peptide(&self) -> Peptide {
+        let mut out = Peptide::empty();
+        for (node, pos) in self.nodes.iter().zip(&self.positions) {
+            let mut children = Directions::empty();
+            for child in &node.children {
+                let dir = Direction::from_delta(self.positions[*child] - pos).unwrap();
+                children.set(dir);
+            }
+
+            let amino = Amino {
+                amino: node.amino,
+                children,
+            };
+            out.inner.insert(*pos, amino);
+        }
+
+        out
+    }
+
+    // returns the lowest energy fold that was seen
+    pub fn run(&mut self, schedule: &Schedule, rng: &mut impl Rng) -> (Peptide, f32) {
+        let mut best = (self.peptide(), self.energy);
+        for step in 0..schedule.steps {
+            let temperature = schedule.temperature(step);
+            if self.step(temperature, rng) && self.energy < best.1 {
+                best = (self.peptide(), self.energy);
+            }
+        }
+
+        best
+    }
+
+    // returns whether the fold changed
+    pub fn step(&mut self, temperature: f32, rng: &mut impl Rng) -> bool {
+        let node = rng.random_range(0..self.nodes.len());
+        let moves = match *MOVES.choose(rng).unwrap() {
+            Move::End => self.end_move(node, rng),
+            Move::Corner => self.corner_flip(node),
+            Move::Crankshaft => self.crankshaft(node),
+            Move::Pull => self.pull_move(node, rng),
+            Move::Pivot => self.pivot(node, rng),
+            Move::Swap => self.swap(node, rng),
+        };
+
+        let Some(moves) = moves.filter(|x| self.is_valid(x)) else {
+            return false;
+        };
+
+        let old = self.apply(&moves);
+        let energy = self.peptide().score();
+        let delta = energy - self.energy;
+        if delta <= 0.0 || rng.random::<f32>() < (-delta / temperature).exp() {
+            self.energy = energy;
+            true
+        } else {
+            self.apply(&old);
+            false
+        }
+    }
+
+    // moves a leaf to another free cell next to its parent
+    fn end_move(&self, node: usize, rng: &mut impl Rng) -> Option<Vec<(usize, Vector2<i32>)>> {
+        if !self.nodes[node].children.is_empty() {
+            return None;
+        }
+
+        let parent = self.positions[self.nodes[node].parent?];
+        let free = (Direction::ALL.iter())
+            .map(|dir| parent + dir.delta())
+            .filter(|pos| !self.occupied.contains_key(pos))
+            .collect::<Vec<_>>();
+        Some(vec![(node, *free.choose(rng)?)])
+    }
+
+    // flips a residue sitting on the corner of a bend to the opposite corner
+    fn corner_flip(&self, node: usize) -> Option<Vec<(usize, Vector2<i32>)>> {
+        let [child] = self.nodes[node].children[..] else {
+            return None;
+        };
+
+        let parent = self.positions[self.nodes[node].parent?];
+        let (pos, child) = (self.positions[node], self.positions[child]);
+        let diagonal = parent - child;
+        (diagonal.x.abs() == 1 && diagonal.y.abs() == 1).then(|| vec![(node, parent + child - pos)])
+    }
+
+    // rotates a two residue U-turn to the other side of its base
+    fn crankshaft(&self, node: usize) -> Option<Vec<(usize, Vector2<i32>)>> {
+        let [next] = self.nodes[node].children[..] else {
+            return None;
+        };
+        let [last] = self.nodes[next].children[..] else {
+            return None;
+        };
+
+        let parent = self.positions[self.nodes[node].parent?];
+        let offset = self.positions[node] - parent;
+        let base = self.positions[last] - parent;
+        let adjacent = base.x.abs() + base.y.abs() == 1;
+        (adjacent && self.positions[next] - self.positions[last] == offset).then(|| {
+            vec![
+                (node, parent - offset),
+                (next, self.positions[last] - offset),
+            ]
+        })
+    }
+
+    // pulls a residue to a free cell diagonal to it and drags its descendants
+    // along the vacated cells until the chain is connected again
+    fn pull_move(&self, node: usize, rng: &mut impl Rng) -> Option<Vec<(usize, Vector2<i32>)>> {
+        let parent = self.positions[self.nodes[node].parent?];
+        let pos = self.positions[node];
+
+        let targets = (Direction::ALL.iter())
+            .map(|dir| parent + dir.delta())
+            .filter(|x| {
+                let delta = x - pos;
+                delta.x.abs() == 1 && delta.y.abs() == 1 && !self.occupied.contains_key(x)
+            })
+            .collect::<Vec<_>>();
+        let target = *targets.choose(rng)?;
+        let corner = pos + (target - parent);
+
+        let mut moves = vec![(node, target)];
+        let mut vacated = vec![pos];
+        let mut current = node;
+        loop {
+            let new_pos = moves.last().unwrap().1;
+            let mut stranded = (self.nodes[current].children.iter())
+                .filter(|x| !adjacent(self.positions[**x], new_pos));
+            let Some(&child) = stranded.next() else {
+                break;
+            };
+
+            if stranded.next().is_some() {
+                return None;
+            }
+
+            let next = match vacated.len() {
+                1 => corner,
+                n => vacated[n - 2],
+            };
+            moves.push((child, next));
+            vacated.push(self.positions[child]);
+            current = child;
+        }
+
+        Some(moves)
+    }
+
+    // rotates or mirrors a whole branch around its parent. without this, the
+    // children of a fully surrounded residue could never trade places
+    fn pivot(&self, node: usize, rng: &mut impl Rng) -> Option<Vec<(usize, Vector2<i32>)>> {
+        let parent = self.nodes[node].parent?;
+        Some(self.rotate(node, parent, rng.random_range(1..8)))
+    }
+
+    // exchanges two sibling branches, rotating each into the other's place
+    fn swap(&self, node: usize, rng: &mut impl Rng) -> Option<Vec<(usize, Vector2<i32>)>> {
+        let children = &self.nodes[node].children;
+        let a = *children.choose(rng)?;
+        let b = *children.choose(rng)?;
+        if a == b {
+            return None;
+        }
+
+        let pos = self.positions[node];
+        let (a_offset, b_offset) = (self.positions[a] - pos, self.positions[b] - pos);
+        let symmetry = (1..4).find(|x| transform(a_offset, *x) == b_offset)?;
+
+        let mut moves = self.rotate(a, node, symmetry);
+        moves.extend(self.rotate(b, node, 4 - symmetry));
+        Some(moves)
+    }
+
+    // applies a lattice symmetry to the branch starting at `node`, centered on
+    // the position of `pivot`
+    fn rotate(&self, node: usize, pivot: usize, symmetry: u8) -> Vec<(usize, Vector2<i32>)> {
+        let pivot = self.positions[pivot];
+
+        let mut moves = Vec::new();
+        let mut stack = vec![node];
+        while let Some(node) = stack.pop() {
+            let offset = self.positions[node] - pivot;
+            moves.push((node, pivot + transform(offset, symmetry)));
+            stack.extend(&self.nodes[node].children);
+        }
+
+        moves
+    }
+
+    fn is_valid(&self, moves: &[(usize, Vector2<i32>)]) -> bool {
+        let mut placed = HashMap::new();
+        for (node, pos) in moves {
+            if placed.insert(*pos, *node).is_some() {
+                return false;
+            }
+
+            let blocked = self.occupied.get(pos);
+            if blocked.is_some_and(|x| !moves.iter().any(|(node, _)| node == x)) {
+                return false;
+            }
+        }
+
+        let position = |node: usize| {
+            (moves.iter().find(|x| x.0 == node))
+                .map(|x| x.1)
+                .unwrap_or(self.positions[node])
+        };
+
+        moves.iter().all(|(node, pos)| {
+            let node = &self.nodes[*node];
+            let parent = node.parent.is_none_or(|x| adjacent(position(x), *pos));
+            parent && node.children.iter().all(|x| adjacent(position(*x), *pos))
+        })
+    }
+
+    // returns the moves needed to undo this one
+    fn apply(&mut self, moves: &[(usize, Vector2<i32>)]) -> Vec<(usize, Vector2<i32>)> {
+        let old = (moves.iter())
+            .map(|(node, _)| (*node, self.positions[*node]))
+            .collect::<Vec<_>>();
+
+        for (node, _) in moves {
+            self.occupied.remove(&self.positions[*node]);
+        }
+
+        for (node, pos) in moves {
+            self.positions[*node] = *pos;
+            self.occupied.insert(*pos, *node);
+        }
+
+        old
+    }
+}
+
+impl Level {
+    pub fn anneal(&self, schedule: &Schedule, rng: &mut impl Rng) -> (Peptide, f32) {
+        Annealer::new(&self.peptide).run(schedule, rng)
+    }
+}
+
+fn adjacent(a: Vector2<i32>, b: Vector2<i32>) -> bool {
+    let delta = a - b;
+    delta.x.abs() + delta.y.abs() == 1
+}
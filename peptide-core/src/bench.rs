@@ -0,0 +1,128 @@
+use std::{
+    fmt::{self, Display},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    agent::Agent,
+    generator::{ConfigError, GeneratorConfig},
+    level::{LEVELS, Level, WIN_THRESHOLD},
+    peptide::Peptide,
+};
+
+pub const MAX_ACTIONS: usize = 10_000;
+
+pub struct Run {
+    pub title: String,
+    pub energy: f32,
+    pub score: f32,
+    pub complete: bool,
+    pub solved: bool,
+    pub actions: usize,
+    pub illegal: usize,
+    pub time: Duration,
+}
+
+pub struct Report {
+    pub agent: String,
+    pub runs: Vec<Run>,
+}
+
+pub fn play(agent: &mut dyn Agent, level: &Level) -> Run {
+    let start = Instant::now();
+    agent.start(level);
+
+    let mut peptide = Peptide::for_level(level);
+    let (mut actions, mut illegal) = (0, 0);
+    while actions < MAX_ACTIONS
+        && let Some(action) = agent.act(level, &peptide)
+    {
+        actions += 1;
+        illegal += level.apply(&mut peptide, action).is_err() as usize;
+    }
+
+    let energy = peptide.score();
+    let score = level.score(energy);
+    let complete = peptide.inner.len() == level.peptide.inner.len();
+    Run {
+        title: level.title.clone(),
+        energy,
+        score,
+        complete,
+        solved: complete && score >= WIN_THRESHOLD,
+        actions,
+        illegal,
+        time: start.elapsed(),
+    }
+}
+
+pub fn benchmark(
+    agent: &mut dyn Agent,
+    config: &GeneratorConfig,
+    random: u64,
+    seed: u64,
+) -> Result<Report, ConfigError> {
+    let campaign = LEVELS.iter().filter_map(|x| x.as_ref().ok().cloned());
+    let generated = (0..random)
+        .map(|i| Level::generate_with(config, seed.wrapping_add(i)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Report {
+        agent: agent.name().to_owned(),
+        runs: campaign.chain(generated).map(|x| play(agent, &x)).collect(),
+    })
+}
+
+impl Report {
+    pub fn solve_rate(&self) -> f32 {
+        let solved = self.runs.iter().filter(|x| x.solved).count();
+        solved as f32 / self.runs.len().max(1) as f32
+    }
+
+    // incomplete folds count as zero
+    pub fn mean_score(&self) -> f32 {
+        let scores = (self.runs.iter()).map(|x| if x.complete { x.score } else { 0.0 });
+        scores.sum::<f32>() / self.runs.len().max(1) as f32
+    }
+
+    pub fn time(&self) -> Duration {
+        self.runs.iter().map(|x| x.time).sum()
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<16} {:>9} {:>7} {:>8} {:>8} {:>10}",
+            "level", "energy", "score", "actions", "illegal", "time"
+        )?;
+        for run in &self.runs {
+            let status = match (run.solved, run.complete) {
+                (true, _) => "",
+                (false, true) => " (not solved)",
+                (false, false) => " (incomplete)",
+            };
+            writeln!(
+                f,
+                "{:<16} {:>9.2} {:>6}% {:>8} {:>8} {:>10.2?}{status}",
+                run.title,
+                run.energy,
+                (run.score * 100.0).round() as i32,
+                run.actions,
+                run.illegal,
+                run.time
+            )?;
+        }
+
+        write!(
+            f,
+            "\n{}: solved {:.0}% of {} levels, mean score {:.0}%, took {:.2?}",
+            self.agent,
+            self.solve_rate() * 100.0,
+            self.runs.len(),
+            self.mean_score() * 100.0,
+            self.time()
+        )
+    }
+}
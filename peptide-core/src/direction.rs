@@ -1,9 +1,12 @@
-use std::ops::BitOr;
+use std::{
+    fmt::{self, Display},
+    ops::BitOr,
+};
 
-use engine::exports::nalgebra::Vector2;
-use serde::Serialize;
+use nalgebra::Vector2;
+use serde::{Serialize, Serializer};
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
 pub enum Direction {
     Up,
@@ -12,7 +15,7 @@ pub enum Direction {
     Right,
 }
 
-#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, Serialize)]
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Directions {
     inner: u8,
 }
@@ -34,6 +37,19 @@ impl Direction {
         }
     }
 
+    pub fn turn_left(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Left => Direction::Down,
+            Direction::Down => Direction::Right,
+            Direction::Right => Direction::Up,
+        }
+    }
+
+    pub fn turn_right(self) -> Direction {
+        self.turn_left().opposite()
+    }
+
     pub fn horizontal(&self) -> bool {
         matches!(self, Direction::Left | Direction::Right)
     }
@@ -47,6 +63,15 @@ impl Direction {
         }
     }
 
+    pub fn letter(&self) -> char {
+        match self {
+            Direction::Up => 'U',
+            Direction::Down => 'D',
+            Direction::Left => 'L',
+            Direction::Right => 'R',
+        }
+    }
+
     pub fn from_delta(delta: Vector2<i32>) -> Option<Self> {
         match delta.as_slice() {
             [1, 0] => Some(Direction::Right),
@@ -95,3 +120,27 @@ impl BitOr<Direction> for Directions {
         }
     }
 }
+
+// same format as the level files, like "RU"
+impl Display for Directions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const ORDER: [Direction; 4] = [
+            Direction::Right,
+            Direction::Up,
+            Direction::Down,
+            Direction::Left,
+        ];
+
+        for dir in ORDER.into_iter().filter(|x| self.contains(*x)) {
+            write!(f, "{}", dir.letter())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Serialize for Directions {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
@@ -0,0 +1,145 @@
+use nalgebra::Vector2;
+
+use crate::{
+    agent::Action,
+    amino::AminoType,
+    direction::Direction,
+    generator::{ConfigError, GeneratorConfig},
+    level::Level,
+    peptide::Peptide,
+};
+
+pub const MAX_RESIDUES: usize = 16;
+pub const GRID_SIZE: usize = 2 * MAX_RESIDUES - 1;
+pub const CHANNELS: usize = AminoType::ALL.len();
+// placed flag, x, y and the parent's slot, then the residue type one hot
+pub const RESIDUE_FEATURES: usize = 4 + AminoType::ALL.len();
+// slots are indices into the level's tree order, and an action is
+// `(slot * 4 + child) * 4 + dir`, placing the slot's `child`th child from
+// the level on side `dir`, both in `Direction::ALL` order
+pub const ACTIONS: usize = MAX_RESIDUES * SIDES * SIDES;
+pub const ILLEGAL_REWARD: f32 = -1.0;
+
+// children a residue can have, and sides to put them on
+const SIDES: usize = Direction::ALL.len();
+
+pub struct Observation {
+    // one hot residue types as [CHANNELS][GRID_SIZE][GRID_SIZE], with the
+    // root in the middle and the top row first
+    pub grid: Vec<f32>,
+    // [MAX_RESIDUES][RESIDUE_FEATURES] in slot order, zeroed past the end
+    // of the level, with -1 as the root's parent slot
+    pub residues: Vec<f32>,
+    pub mask: Vec<bool>,
+}
+
+pub struct Env {
+    // checked to be valid when the env is made
+    config: GeneratorConfig,
+    pub level: Level,
+    pub peptide: Peptide,
+    // the level's residues, indexed by slot
+    slots: Vec<Vector2<i32>>,
+}
+
+impl Env {
+    pub fn new(config: GeneratorConfig, seed: u64) -> Result<Self, ConfigError> {
+        if *config.residues.end() > MAX_RESIDUES {
+            return Err(ConfigError::TooManyResidues { max: MAX_RESIDUES });
+        }
+
+        let level = Level::generate_with(&config, seed)?;
+        Ok(Self {
+            config,
+            peptide: Peptide::for_level(&level),
+            slots: level.peptide.tree_order(),
+            level,
+        })
+    }
+
+    pub fn reset(&mut self, seed: u64) -> Observation {
+        self.level = Level::generate_with(&self.config, seed).unwrap();
+        self.peptide = Peptide::for_level(&self.level);
+        self.slots = self.level.peptide.tree_order();
+        self.observe()
+    }
+
+    // rewarded with how much the move raised the level's score, and done
+    // once the fold is complete or stuck
+    pub fn step(&mut self, action: usize) -> (Observation, f32, bool) {
+        let before = self.level.score(self.peptide.score());
+        let action = self.decode(action);
+        let legal = action.is_some_and(|x| self.level.apply(&mut self.peptide, x).is_ok());
+
+        let reward = match legal {
+            true => self.level.score(self.peptide.score()) - before,
+            false => ILLEGAL_REWARD,
+        };
+        (self.observe(), reward, self.done())
+    }
+
+    pub fn done(&self) -> bool {
+        self.peptide.inner.len() == self.level.peptide.inner.len() || !self.mask().contains(&true)
+    }
+
+    pub fn observe(&self) -> Observation {
+        let mut grid = vec![0.0; CHANNELS * GRID_SIZE * GRID_SIZE];
+        for (pos, amino) in &self.peptide.inner {
+            let col = (pos.x + MAX_RESIDUES as i32 - 1) as usize;
+            let row = (MAX_RESIDUES as i32 - 1 - pos.y) as usize;
+            grid[(channel(amino.amino) * GRID_SIZE + row) * GRID_SIZE + col] = 1.0;
+        }
+
+        let mut residues = vec![0.0; MAX_RESIDUES * RESIDUE_FEATURES];
+        for (slot, features) in residues.chunks_mut(RESIDUE_FEATURES).enumerate() {
+            let Some(target) = self.slots.get(slot) else {
+                break;
+            };
+
+            if let Some(pos) = self.residue(slot) {
+                features[..3].copy_from_slice(&[1.0, pos.x as f32, pos.y as f32]);
+            }
+
+            let parent = self.level.peptide.parent(*target);
+            let parent = parent.and_then(|(pos, _)| self.slots.iter().position(|x| *x == pos));
+            features[3] = parent.map_or(-1.0, |x| x as f32);
+            features[4 + channel(self.level.get(*target).unwrap().amino)] = 1.0;
+        }
+
+        Observation {
+            grid,
+            residues,
+            mask: self.mask(),
+        }
+    }
+
+    pub fn mask(&self) -> Vec<bool> {
+        let legal = |x| self.level.check(&self.peptide, x).is_ok();
+        (0..ACTIONS)
+            .map(|x| self.decode(x).is_some_and(legal))
+            .collect()
+    }
+
+    // where the slot's residue is in the fold, if it's been placed
+    fn residue(&self, slot: usize) -> Option<Vector2<i32>> {
+        let path = self.level.peptide.path(*self.slots.get(slot)?);
+        self.peptide.find(&path)
+    }
+
+    // the placement an action asks for, which may not be allowed
+    fn decode(&self, action: usize) -> Option<Action> {
+        let slot = action / SIDES / SIDES;
+        let target = *self.slots.get(slot)?;
+        let parent = self.residue(slot)?;
+
+        let children = self.level.get(target)?.children;
+        let child = children.iter().nth(action / SIDES % SIDES)?;
+        let amino = self.level.get(target + child.delta())?.amino;
+        let dir = Direction::ALL[action % SIDES];
+        Some(Action::Place { parent, dir, amino })
+    }
+}
+
+fn channel(amino: AminoType) -> usize {
+    AminoType::ALL.iter().position(|x| *x == amino).unwrap()
+}
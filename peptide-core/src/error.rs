@@ -0,0 +1,86 @@
+use std::{
+    borrow::Cow,
+    fmt::{self, Display},
+};
+
+use crate::{level::Level, peptide::Peptide, validate::Violation};
+
+#[derive(Debug, Clone)]
+pub struct LevelError {
+    pub file: String,
+    // one-based line and column, if the problem could be located
+    pub position: Option<(usize, usize)>,
+    pub kind: LevelErrorKind,
+}
+
+#[derive(Debug, Clone)]
+pub enum LevelErrorKind {
+    Syntax(ron::Error),
+    Invalid(Vec<Violation>),
+}
+
+impl LevelError {
+    pub fn syntax(file: &str, error: ron::error::SpannedError) -> Self {
+        Self {
+            file: file.into(),
+            position: Some((error.span.start.line, error.span.start.col)),
+            kind: LevelErrorKind::Syntax(error.code),
+        }
+    }
+
+    pub fn invalid(file: &str, raw: &[u8], violations: Vec<Violation>) -> Self {
+        let position = violations.iter().find_map(|x| x.pos()).and_then(|pos| {
+            let key = format!("({},{}):", pos.x, pos.y);
+            (String::from_utf8_lossy(raw).lines())
+                .position(|line| {
+                    let line = line.chars().filter(|x| !x.is_whitespace());
+                    line.collect::<String>().starts_with(&key)
+                })
+                .map(|line| (line + 1, 1))
+        });
+
+        Self {
+            file: file.into(),
+            position,
+            kind: LevelErrorKind::Invalid(violations),
+        }
+    }
+
+    // having no residues, it counts as solved so it doesn't block the rest of
+    // a pack
+    pub fn placeholder(&self) -> Level {
+        Level {
+            title: self.file.clone(),
+            description: format!("This level is unavailable.\n\n{self}"),
+            range: (0.0, 0.0),
+            seed: None,
+            peptide: Peptide::empty(),
+        }
+    }
+}
+
+pub fn playable(level: &Result<Level, LevelError>) -> Cow<'_, Level> {
+    match level {
+        Ok(level) => Cow::Borrowed(level),
+        Err(error) => Cow::Owned(error.placeholder()),
+    }
+}
+
+impl Display for LevelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.file)?;
+        if let Some((line, col)) = self.position {
+            write!(f, ":{line}:{col}")?;
+        }
+
+        match &self.kind {
+            LevelErrorKind::Syntax(error) => write!(f, ": {error}"),
+            LevelErrorKind::Invalid(violations) => {
+                for violation in violations {
+                    write!(f, "\n ∙ {violation}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
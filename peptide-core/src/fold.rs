@@ -0,0 +1,195 @@
+use std::fmt::{self, Display};
+
+use nalgebra::Vector2;
+
+use crate::{
+    amino::Amino,
+    direction::{Direction, Directions},
+    level::Level,
+    peptide::Peptide,
+    sequence::{self, Node, SequenceError},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Turn {
+    Forward,
+    Left,
+    Right,
+    // only possible from the root, everything else has its parent behind it
+    Back,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FoldError {
+    MissingTurns,
+    Sequence(SequenceError),
+    UnknownTurn(usize, char),
+    Collision(usize),
+    TooFewTurns,
+    TooManyTurns,
+    WrongPeptide,
+}
+
+impl Turn {
+    pub fn letter(&self) -> char {
+        match self {
+            Turn::Forward => 'F',
+            Turn::Left => 'L',
+            Turn::Right => 'R',
+            Turn::Back => 'B',
+        }
+    }
+
+    pub fn from_letter(letter: char) -> Option<Self> {
+        [Turn::Forward, Turn::Left, Turn::Right, Turn::Back]
+            .into_iter()
+            .find(|x| x.letter() == letter)
+    }
+
+    pub fn between(heading: Direction, dir: Direction) -> Self {
+        if dir == heading {
+            Turn::Forward
+        } else if dir == heading.turn_left() {
+            Turn::Left
+        } else if dir == heading.turn_right() {
+            Turn::Right
+        } else {
+            Turn::Back
+        }
+    }
+
+    pub fn apply(&self, heading: Direction) -> Direction {
+        match self {
+            Turn::Forward => heading,
+            Turn::Left => heading.turn_left(),
+            Turn::Right => heading.turn_right(),
+            Turn::Back => heading.opposite(),
+        }
+    }
+}
+
+impl Peptide {
+    // the peptide in sequence notation with siblings sorted by type, then
+    // after a slash one turn per residue but the root in sequence order,
+    // relative to the bond into its parent and with the root entered going
+    // right, like `RF(DD)L/FLFR`
+    pub fn to_fold(&self) -> String {
+        let (mut sequence, mut turns) = (String::new(), String::new());
+        if self.get(Vector2::zeros()).is_some() {
+            self.write_fold(
+                Vector2::zeros(),
+                Direction::Right,
+                &mut sequence,
+                &mut turns,
+            );
+        }
+
+        format!("{sequence}/{turns}")
+    }
+
+    pub fn from_fold(fold: &str) -> Result<Self, FoldError> {
+        let (sequence, turns) = fold.split_once('/').ok_or(FoldError::MissingTurns)?;
+        let root = sequence::parse(sequence).map_err(FoldError::Sequence)?;
+
+        let mut peptide = Peptide::empty();
+        let mut turns = turns.trim().chars().enumerate();
+        peptide.place(&root, Vector2::zeros(), Direction::Right, &mut turns)?;
+
+        match turns.next() {
+            Some(_) => Err(FoldError::TooManyTurns),
+            None => Ok(peptide),
+        }
+    }
+
+    fn write_fold(
+        &self,
+        pos: Vector2<i32>,
+        heading: Direction,
+        sequence: &mut String,
+        turns: &mut String,
+    ) {
+        let amino = self.get(pos).unwrap();
+        sequence.push(amino.amino.letter());
+
+        let mut children = amino.children.iter().collect::<Vec<_>>();
+        children.sort_by_key(|dir| self.get(pos + dir.delta()).map(|x| x.amino));
+
+        // the last child continues the chain, the rest are branches
+        for (i, dir) in children.iter().enumerate() {
+            let branch = i + 1 < children.len();
+            branch.then(|| sequence.push('('));
+            turns.push(Turn::between(heading, *dir).letter());
+            self.write_fold(pos + dir.delta(), *dir, sequence, turns);
+            branch.then(|| sequence.push(')'));
+        }
+    }
+
+    fn place(
+        &mut self,
+        node: &Node,
+        pos: Vector2<i32>,
+        heading: Direction,
+        turns: &mut impl Iterator<Item = (usize, char)>,
+    ) -> Result<(), FoldError> {
+        let amino = Amino {
+            amino: node.amino,
+            children: Directions::empty(),
+        };
+        self.inner.insert(pos, amino);
+
+        for child in &node.children {
+            let (idx, letter) = turns.next().ok_or(FoldError::TooFewTurns)?;
+            let turn = Turn::from_letter(letter).ok_or(FoldError::UnknownTurn(idx, letter))?;
+
+            let dir = turn.apply(heading);
+            let next = pos + dir.delta();
+            if self.inner.contains_key(&next) {
+                return Err(FoldError::Collision(idx));
+            }
+
+            self.inner.get_mut(&pos).unwrap().children.set(dir);
+            self.place(child, next, dir, turns)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Level {
+    // partial folds are allowed, like the ones players share before finishing
+    pub fn decode_fold(&self, fold: &str) -> Result<Peptide, FoldError> {
+        let peptide = Peptide::from_fold(fold)?;
+
+        let root = |x: &Peptide| x.get(Vector2::zeros()).map(|x| x.amino);
+        if root(&peptide) != root(&self.peptide) {
+            return Err(FoldError::WrongPeptide);
+        }
+
+        // siblings never share a type, so each residue matches its own one
+        for pos in peptide.inner.keys() {
+            if self.peptide.find(&peptide.path(*pos)).is_none() {
+                return Err(FoldError::WrongPeptide);
+            }
+        }
+
+        Ok(peptide)
+    }
+}
+
+impl Display for FoldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FoldError::MissingTurns => f.write_str("fold is missing the '/' before its turns"),
+            FoldError::Sequence(error) => error.fmt(f),
+            FoldError::UnknownTurn(idx, chr) => {
+                write!(f, "turn {}: '{chr}' is not one of F, L, R or B", idx + 1)
+            }
+            FoldError::Collision(idx) => {
+                write!(f, "turn {}: residue runs into another one", idx + 1)
+            }
+            FoldError::TooFewTurns => f.write_str("fold has fewer turns than residues"),
+            FoldError::TooManyTurns => f.write_str("fold has more turns than residues"),
+            FoldError::WrongPeptide => f.write_str("fold is of a different peptide"),
+        }
+    }
+}
@@ -0,0 +1,297 @@
+use std::{
+    fmt::{self, Display},
+    ops::RangeInclusive,
+};
+
+use nalgebra::Vector2;
+use rand::{Rng, SeedableRng, rngs::StdRng, seq::IndexedRandom};
+
+use crate::{
+    amino::{Amino, AminoType, Interaction},
+    analysis::Analysis,
+    direction::Directions,
+    level::Level,
+    peptide::Peptide,
+};
+
+// random mode seeds start with their difficulty, so a seed alone is enough
+// to reproduce its level
+const DIFFICULTY_STRIDE: u64 = 1_000_000_000;
+
+#[derive(Clone)]
+pub struct GeneratorConfig {
+    pub residues: RangeInclusive<usize>,
+    // chance of growing from a random residue instead of the last one placed
+    pub branching: f32,
+    pub distribution: Vec<(AminoType, f32)>,
+
+    pub interactions: Vec<Interaction>,
+    pub solutions: RangeInclusive<usize>,
+    pub max_optimal: usize,
+    pub attempts: u32,
+}
+
+impl GeneratorConfig {
+    pub fn difficulty(solved: u32) -> Self {
+        match solved {
+            0 => Self {
+                residues: 4..=6,
+                branching: 0.0,
+                solutions: 1..=500,
+                ..Self::default()
+            },
+            1..=2 => Self {
+                residues: 6..=8,
+                branching: 0.2,
+                solutions: 50..=2_000,
+                ..Self::default()
+            },
+            3..=5 => Self {
+                residues: 8..=11,
+                branching: 0.5,
+                interactions: vec![Interaction::Hydrophobic],
+                ..Self::default()
+            },
+            // these take seconds to analyze, so don't be too picky
+            _ => Self {
+                residues: 10..=12,
+                solutions: 5_000..=usize::MAX,
+                interactions: vec![Interaction::SaltBridge],
+                attempts: 10,
+                ..Self::default()
+            },
+        }
+    }
+
+    pub fn for_seed(seed: u64) -> Self {
+        Self::difficulty(Self::seed_difficulty(seed))
+    }
+
+    pub fn seed_difficulty(seed: u64) -> u32 {
+        (seed / DIFFICULTY_STRIDE) as u32
+    }
+
+    pub fn random_seed(solved: u32, rng: &mut impl Rng) -> u64 {
+        solved as u64 * DIFFICULTY_STRIDE + rng.random_range(0..DIFFICULTY_STRIDE)
+    }
+
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.residues.is_empty() {
+            return Err(ConfigError::NoResidues);
+        }
+
+        let mut weights = self.distribution.iter().map(|x| x.1);
+        let total = weights.clone().sum::<f32>();
+        if weights.any(|x| !x.is_finite() || x < 0.0) || !total.is_normal() {
+            return Err(ConfigError::NoAminos);
+        }
+
+        Ok(())
+    }
+
+    // only called on configs that passed `validate`
+    pub(crate) fn amino(&self, rng: &mut impl Rng) -> AminoType {
+        self.distribution.choose_weighted(rng, |x| x.1).unwrap().0
+    }
+
+    pub fn check(&self, analysis: &Analysis) -> Result<(), Rejection> {
+        let (min, max) = analysis.range;
+        if max - min < 1.0 {
+            return Err(Rejection::FlatRange(max - min));
+        }
+
+        if analysis.winning >= analysis.complete {
+            return Err(Rejection::EveryFoldWins);
+        }
+
+        if analysis.optimal > self.max_optimal {
+            return Err(Rejection::TooManyOptima(analysis.optimal));
+        }
+
+        let interactions = analysis.optimum.interactions();
+        if interactions.is_empty() {
+            return Err(Rejection::NoInteraction);
+        }
+
+        if !self.solutions.contains(&analysis.complete) {
+            return Err(Rejection::Solutions(analysis.complete));
+        }
+
+        for interaction in &self.interactions {
+            if !interactions.iter().any(|x| x.2 == *interaction) {
+                return Err(Rejection::MissingInteraction(*interaction));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum ConfigError {
+    NoResidues,
+    NoAminos,
+    TooManyResidues { max: usize },
+}
+
+#[derive(Debug, Clone)]
+pub enum GenerateError {
+    Config(ConfigError),
+    Rejected(Vec<Rejection>),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Rejection {
+    Unfoldable,
+    FlatRange(f32),
+    EveryFoldWins,
+    TooManyOptima(usize),
+    NoInteraction,
+    Solutions(usize),
+    MissingInteraction(Interaction),
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rejection::Unfoldable => f.write_str("no fold places every residue"),
+            Rejection::FlatRange(spread) => write!(f, "energy range is only {spread:.2} wide"),
+            Rejection::EveryFoldWins => f.write_str("every complete fold wins"),
+            Rejection::TooManyOptima(count) => write!(f, "{count} distinct optimal folds"),
+            Rejection::NoInteraction => f.write_str("optimal fold has no interactions"),
+            Rejection::Solutions(count) => write!(f, "{count} complete folds is out of range"),
+            Rejection::MissingInteraction(interaction) => {
+                write!(f, "optimal fold has no {interaction:?} interaction")
+            }
+        }
+    }
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::NoResidues => f.write_str("residue count range is empty"),
+            ConfigError::NoAminos => f.write_str("residue distribution has no valid weights"),
+            ConfigError::TooManyResidues { max } => {
+                write!(f, "levels can have at most {max} residues")
+            }
+        }
+    }
+}
+
+impl Display for GenerateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GenerateError::Config(error) => error.fmt(f),
+            GenerateError::Rejected(rejections) => {
+                let reasons = rejections.iter().map(ToString::to_string);
+                write!(
+                    f,
+                    "every level was rejected: {}",
+                    reasons.collect::<Vec<_>>().join(", ")
+                )
+            }
+        }
+    }
+}
+
+impl Default for GeneratorConfig {
+    fn default() -> Self {
+        Self {
+            residues: 5..=13,
+            branching: 1.0,
+            distribution: AminoType::ALL.iter().map(|x| (*x, 1.0)).collect(),
+
+            interactions: Vec::new(),
+            solutions: 0..=usize::MAX,
+            max_optimal: 8,
+            attempts: 50,
+        }
+    }
+}
+
+impl Level {
+    pub fn generate(seed: u64) -> Level {
+        // the default config is always valid
+        Self::generate_with(&GeneratorConfig::default(), seed).unwrap()
+    }
+
+    // counts up from `seed` until a level passes the config's checks
+    pub fn generate_with(config: &GeneratorConfig, seed: u64) -> Result<Level, ConfigError> {
+        Self::generate_reporting(config, seed, |_, _| {})
+    }
+
+    pub fn generate_reporting(
+        config: &GeneratorConfig,
+        seed: u64,
+        mut report: impl FnMut(u64, &[Rejection]),
+    ) -> Result<Level, ConfigError> {
+        let mut seed = seed;
+        loop {
+            match Self::generate_checked(config, seed) {
+                Ok(level) => return Ok(level),
+                Err(GenerateError::Config(error)) => return Err(error),
+                Err(GenerateError::Rejected(rejections)) => report(seed, &rejections),
+            }
+            seed = seed.wrapping_add(1);
+        }
+    }
+
+    pub fn generate_checked(config: &GeneratorConfig, seed: u64) -> Result<Level, GenerateError> {
+        config.validate().map_err(GenerateError::Config)?;
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        let mut rejections = Vec::new();
+        for _ in 0..config.attempts.max(1) {
+            let mut level = Self::generate_once(config, seed, &mut rng);
+            let Some(analysis) = level.analyze() else {
+                rejections.push(Rejection::Unfoldable);
+                continue;
+            };
+            level.range = analysis.range;
+
+            match config.check(&analysis) {
+                Ok(()) => return Ok(level),
+                Err(rejection) => rejections.push(rejection),
+            }
+        }
+
+        Err(GenerateError::Rejected(rejections))
+    }
+
+    fn generate_once(config: &GeneratorConfig, seed: u64, rng: &mut impl Rng) -> Level {
+        const PREFIX: &[&str] = &[
+            "BPC", "PT", "MET", "CJC", "DSIP", "Mots", "SS", "LL", "ARA", "TZP",
+        ];
+
+        let title = format!(
+            "{}-{}",
+            PREFIX.choose(rng).unwrap(),
+            rng.random_range(1..=999)
+        );
+
+        let mut peptide = Peptide::empty();
+        peptide.inner.insert(
+            Vector2::zeros(),
+            Amino {
+                amino: config.amino(rng),
+                children: Directions::empty(),
+            },
+        );
+
+        let mut tip = Vector2::zeros();
+        for _ in 1..rng.random_range(config.residues.clone()) {
+            tip = peptide.mutate(rng, config, tip);
+        }
+
+        Level {
+            title,
+            description: format!(
+                "This level was procedurally generated from seed {seed}... Good luck."
+            ),
+            range: (0.0, 0.0),
+            seed: Some(seed),
+            peptide,
+        }
+    }
+}
@@ -0,0 +1,269 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    sync::LazyLock,
+};
+
+use nalgebra::Vector2;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    amino::{Amino, AminoType},
+    direction::{Direction, Directions},
+    error::LevelError,
+    peptide::Peptide,
+    sequence::parse_peptide,
+};
+
+macro include_level($name:expr) {
+    include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/levels/", $name))
+}
+
+const RAW_LEVELS: &[(&str, &[u8])] = &[
+    ("level_1.ron", include_level!("level_1.ron")),
+    ("level_2.ron", include_level!("level_2.ron")),
+    ("level_3.ron", include_level!("level_3.ron")),
+    ("level_4.ron", include_level!("level_4.ron")),
+    ("level_5.ron", include_level!("level_5.ron")),
+    ("level_6.ron", include_level!("level_6.ron")),
+];
+
+pub const MANIFEST: &str = "pack.ron";
+
+pub const WIN_THRESHOLD: f32 = 0.95;
+
+// solved energies wobble a bit with the order residues are summed in
+const RANGE_TOLERANCE: f32 = 1e-3;
+
+pub static LEVELS: LazyLock<Vec<Result<Level, LevelError>>> = LazyLock::new(|| {
+    RAW_LEVELS
+        .iter()
+        .map(|(file, raw)| Level::load(file, raw))
+        .collect::<Vec<_>>()
+});
+
+pub fn verify_levels() -> Vec<String> {
+    (RAW_LEVELS.iter())
+        .filter_map(|(file, raw)| verify_level(file, raw))
+        .collect()
+}
+
+pub fn verify_level(file: &str, raw: &[u8]) -> Option<String> {
+    let level = match Level::load(file, raw) {
+        Ok(level) => level,
+        Err(error) => return Some(error.to_string()),
+    };
+
+    let (stored, solved) = (level.range, level.solve());
+    let matches = (stored.0 - solved.0).abs() <= RANGE_TOLERANCE
+        && (stored.1 - solved.1).abs() <= RANGE_TOLERANCE;
+    (!matches).then(|| {
+        format!(
+            "{file} ({}):\n  - range: ({:?}, {:?})\n  + range: ({:?}, {:?})",
+            level.title, stored.0, stored.1, solved.0, solved.1
+        )
+    })
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Level {
+    pub title: String,
+    pub description: String,
+    // computed on load when left out of the file
+    #[serde(default = "unsolved")]
+    pub range: (f32, f32),
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+
+    #[serde(deserialize_with = "parse_peptide")]
+    pub peptide: Peptide,
+}
+
+// marks the range as missing so it gets solved in `Level::load`
+fn unsolved() -> (f32, f32) {
+    (f32::NAN, f32::NAN)
+}
+
+pub(crate) fn normalize(range: (f32, f32), energy: f32) -> f32 {
+    // every complete fold is optimal, so don't divide by zero
+    if range.0 >= range.1 {
+        return 1.0;
+    }
+
+    (energy - range.1) / (range.0 - range.1)
+}
+
+impl Level {
+    pub fn load(file: &str, raw: &[u8]) -> Result<Self, LevelError> {
+        let mut level =
+            ron::de::from_bytes::<Self>(raw).map_err(|x| LevelError::syntax(file, x))?;
+        let violations = level.peptide.validate();
+        if !violations.is_empty() {
+            return Err(LevelError::invalid(file, raw, violations));
+        }
+
+        if level.range.0.is_nan() {
+            level.range = level.solve();
+        }
+
+        Ok(level)
+    }
+
+    // loading the result gives back the same level, though the residues are
+    // in tree order rather than the hand written order of the bundled files
+    pub fn to_ron(&self) -> String {
+        // ron escapes apostrophes, which the level files leave alone
+        let string = |x: &String| ron::to_string(x).unwrap().replace("\\'", "'");
+
+        let mut out = String::from("Level(\n");
+        out.push_str(&format!("    title: {},\n", string(&self.title)));
+        out.push_str(&format!(
+            "    description: {},\n",
+            string(&self.description)
+        ));
+        out.push_str(&format!(
+            "    range: ({:?}, {:?}),\n",
+            self.range.0, self.range.1
+        ));
+        if let Some(seed) = self.seed {
+            out.push_str(&format!("    seed: Some({seed}),\n"));
+        }
+
+        let residues = (self.peptide.tree_order().into_iter())
+            .map(|pos| {
+                let amino = self.peptide.get(pos).unwrap();
+                format!(
+                    "        ({}, {}): Amino(amino: {:?}, children: \"{}\")",
+                    pos.x, pos.y, amino.amino, amino.children
+                )
+            })
+            .collect::<Vec<_>>();
+
+        out.push_str("\n    peptide: Peptide(inner: {\n");
+        out.push_str(&residues.join(",\n"));
+        out.push_str("\n    })\n)\n");
+
+        out
+    }
+
+    pub fn get(&self, pos: Vector2<i32>) -> Option<&Amino> {
+        self.peptide.get(pos)
+    }
+
+    // starts at the `child_idx`th child and skips over any already placed
+    pub fn next_child(
+        &self,
+        peptide: &Peptide,
+        selected: Vector2<i32>,
+        child_idx: u8,
+    ) -> Option<Vector2<i32>> {
+        let level_pos = self.peptide.find(&peptide.path(selected))?;
+        let level = self.get(level_pos)?;
+
+        let dirs = level.children.iter();
+        for next_dir in dirs.cycle().skip(child_idx as usize).take(4) {
+            let next_pos = level_pos + next_dir.delta();
+            let Some(next) = self.get(next_pos) else {
+                continue;
+            };
+
+            let max = self.peptide.children_of_type(level_pos, next.amino);
+            let current = peptide.children_of_type(selected, next.amino);
+            if current < max {
+                return Some(next_pos);
+            }
+        }
+
+        None
+    }
+}
+
+impl Level {
+    pub fn solve(&self) -> (f32, f32) {
+        self.solution().0
+    }
+
+    pub fn optimal(&self) -> Peptide {
+        self.solution().1
+    }
+
+    pub fn solution(&self) -> ((f32, f32), Peptide) {
+        let (mut best, mut max) = ((Peptide::for_level(self), f32::MAX), f32::MIN);
+        self.explore(|peptide, score| {
+            max = max.max(score);
+            if peptide.inner.len() == self.peptide.inner.len() && score < best.1 {
+                best = (peptide.clone(), score);
+            }
+        });
+
+        ((best.1, max), best.0)
+    }
+
+    pub fn score(&self, energy: f32) -> f32 {
+        normalize(self.range, energy)
+    }
+
+    // visits every distinct peptide that can be built for this level
+    pub(super) fn explore(&self, mut visit: impl FnMut(&Peptide, f32)) {
+        let mut seen = HashSet::new();
+        let mut queue = VecDeque::new();
+        queue.push_back(Peptide::for_level(self));
+
+        while let Some(peptide) = queue.pop_front() {
+            if !seen.insert(peptide.clone()) {
+                continue;
+            }
+
+            for (amino, pos, dir) in self.options(&peptide) {
+                let amino = Amino {
+                    amino,
+                    children: Directions::empty(),
+                };
+
+                let mut peptide = peptide.clone();
+                (peptide.inner.get_mut(&(pos - dir.delta())).unwrap())
+                    .children
+                    .set(dir);
+                peptide.inner.insert(pos, amino);
+                queue.push_back(peptide);
+            }
+
+            visit(&peptide, peptide.score());
+        }
+    }
+
+    // enumerates all possible amino acids that can be added to the peptide
+    pub fn options(&self, peptide: &Peptide) -> Vec<(AminoType, Vector2<i32>, Direction)> {
+        let mut out = Vec::new();
+
+        for pos in peptide.inner.keys() {
+            let path = peptide.path(*pos);
+            let Some(level_pos) = self.peptide.find(&path) else {
+                continue;
+            };
+            let level = self.get(level_pos).unwrap();
+
+            for child in level.children.iter() {
+                let Some(amino) = self.get(level_pos + child.delta()) else {
+                    continue;
+                };
+
+                let max = self.peptide.children_of_type(level_pos, amino.amino);
+                let current = peptide.children_of_type(*pos, amino.amino);
+                if current >= max {
+                    continue;
+                }
+
+                for dir in Direction::ALL {
+                    let next = pos + dir.delta();
+                    if peptide.inner.contains_key(&next) {
+                        continue;
+                    }
+
+                    out.push((amino.amino, next, dir));
+                }
+            }
+        }
+
+        out
+    }
+}
@@ -0,0 +1,40 @@
+#![feature(decl_macro)]
+
+use nalgebra::Vector2;
+
+pub mod agent;
+pub mod amino;
+pub mod analysis;
+pub mod anneal;
+pub mod bench;
+pub mod direction;
+pub mod env;
+pub mod error;
+pub mod fold;
+pub mod generator;
+pub mod level;
+pub mod metrics;
+pub mod pdb;
+pub mod peptide;
+pub mod protocol;
+pub mod sequence;
+pub mod share;
+pub mod text;
+pub mod validate;
+
+// one of the eight rotations and reflections of the lattice, zero being the
+// identity
+pub fn transform(pos: Vector2<i32>, symmetry: u8) -> Vector2<i32> {
+    let pos = if symmetry >= 4 {
+        Vector2::new(-pos.x, pos.y)
+    } else {
+        pos
+    };
+
+    match symmetry % 4 {
+        0 => pos,
+        1 => Vector2::new(-pos.y, pos.x),
+        2 => -pos,
+        _ => Vector2::new(pos.y, -pos.x),
+    }
+}
@@ -0,0 +1,113 @@
+use std::fmt::Write;
+
+use nalgebra::Vector2;
+use serde::Serialize;
+
+use crate::{amino::AminoType, peptide::Peptide};
+
+#[derive(Serialize)]
+pub struct Metrics {
+    pub energy: f32,
+    pub radius_of_gyration: f32,
+    pub bounds: (Vector2<i32>, Vector2<i32>),
+    pub residues: Vec<Residue>,
+    pub contacts: Vec<Contact>,
+}
+
+#[derive(Serialize)]
+pub struct Residue {
+    pub pos: Vector2<i32>,
+    pub amino: AminoType,
+    // `None` if every side is bonded
+    pub exposure: Option<f32>,
+}
+
+#[derive(Serialize)]
+pub struct Contact {
+    pub a: Vector2<i32>,
+    pub b: Vector2<i32>,
+    pub amino_a: AminoType,
+    pub amino_b: AminoType,
+    // what the pair adds to the peptide's score
+    pub energy: f32,
+}
+
+impl Peptide {
+    pub fn metrics(&self) -> Metrics {
+        let order = self.tree_order();
+
+        let residues = (order.iter())
+            .map(|pos| Residue {
+                pos: *pos,
+                amino: self.get(*pos).unwrap().amino,
+                exposure: self.percent_cover(*pos).map(|x| 1.0 - x),
+            })
+            .collect();
+
+        let adjacency = |a: AminoType, b: AminoType| {
+            let bonus = a.adjacency().iter().find(|x| x.0 == b);
+            bonus.map_or(0.0, |x| x.1 as f32 / 2.0)
+        };
+        let contacts = (self.contacts().into_iter())
+            .map(|(a, b)| {
+                let (amino_a, amino_b) = (self.get(a).unwrap().amino, self.get(b).unwrap().amino);
+                Contact {
+                    a,
+                    b,
+                    amino_a,
+                    amino_b,
+                    energy: adjacency(amino_a, amino_b) + adjacency(amino_b, amino_a),
+                }
+            })
+            .collect();
+
+        let count = self.inner.len().max(1) as f32;
+        let centroid = self
+            .inner
+            .keys()
+            .map(|x| x.map(|x| x as f32))
+            .sum::<Vector2<f32>>()
+            / count;
+        let spread = (self.inner.keys())
+            .map(|x| (x.map(|x| x as f32) - centroid).norm_squared())
+            .sum::<f32>();
+
+        Metrics {
+            energy: self.score(),
+            radius_of_gyration: (spread / count).sqrt(),
+            bounds: self.bounds(),
+            residues,
+            contacts,
+        }
+    }
+}
+
+impl Metrics {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    pub fn contacts_csv(&self) -> String {
+        let mut out = String::from("ax,ay,bx,by,amino_a,amino_b,energy\n");
+        for contact in &self.contacts {
+            let (a, b) = (contact.a, contact.b);
+            writeln!(
+                out,
+                "{},{},{},{},{:?},{:?},{}",
+                a.x, a.y, b.x, b.y, contact.amino_a, contact.amino_b, contact.energy
+            )
+            .unwrap();
+        }
+        out
+    }
+
+    pub fn residues_csv(&self) -> String {
+        let mut out = String::from("x,y,amino,exposure\n");
+        for residue in &self.residues {
+            let exposure = residue.exposure.map(|x| x.to_string()).unwrap_or_default();
+            let pos = residue.pos;
+            writeln!(out, "{},{},{:?},{exposure}", pos.x, pos.y, residue.amino).unwrap();
+        }
+        out
+    }
+}
@@ -0,0 +1,71 @@
+use std::{collections::HashMap, fmt::Write};
+
+use crate::{amino::Interaction, peptide::Peptide};
+
+// between bonded alpha carbons, in ångströms
+pub const BOND_LENGTH: f32 = 3.8;
+
+impl Peptide {
+    // one alpha carbon per residue numbered in tree order, with CONECT
+    // records for bonds and disulfides
+    pub fn to_pdb(&self) -> String {
+        let order = self.tree_order();
+        let serials = (order.iter().enumerate())
+            .map(|(i, pos)| (*pos, i + 1))
+            .collect::<HashMap<_, _>>();
+
+        let disulfides = (self.interactions().into_iter())
+            .filter(|x| x.2 == Interaction::Disulfide)
+            .map(|(a, b, _)| {
+                let (a, b) = (serials[&a], serials[&b]);
+                (a.min(b), a.max(b))
+            })
+            .collect::<Vec<_>>();
+
+        let mut out = String::new();
+        for (i, (a, b)) in disulfides.iter().enumerate() {
+            writeln!(out, "SSBOND {:>3} CYS A {a:>4}    CYS A {b:>4}", i + 1).unwrap();
+        }
+
+        for (pos, serial) in order.iter().zip(1..) {
+            let name = format!("{:?}", self.get(*pos).unwrap().amino).to_uppercase();
+            // the lattice is flat, so everything is at z = 0
+            let (x, y, z) = (pos.x as f32 * BOND_LENGTH, pos.y as f32 * BOND_LENGTH, 0.0);
+            write!(out, "ATOM  {serial:>5}  CA  {name} A{serial:>4}    ").unwrap();
+            writeln!(out, "{x:>8.3}{y:>8.3}{z:>8.3}  1.00  0.00           C").unwrap();
+        }
+
+        for pos in &order {
+            let mut bonded = Vec::new();
+            if let Some((parent, _)) = self.parent(*pos) {
+                bonded.push(serials[&parent]);
+            }
+
+            let amino = self.get(*pos).unwrap();
+            bonded.extend(
+                amino
+                    .children
+                    .iter()
+                    .map(|dir| serials[&(pos + dir.delta())]),
+            );
+
+            let serial = serials[pos];
+            for (a, b) in &disulfides {
+                if *a == serial {
+                    bonded.push(*b);
+                } else if *b == serial {
+                    bonded.push(*a);
+                }
+            }
+
+            if !bonded.is_empty() {
+                write!(out, "CONECT{serial:>5}").unwrap();
+                bonded.iter().for_each(|x| write!(out, "{x:>5}").unwrap());
+                out.push('\n');
+            }
+        }
+
+        out.push_str("END\n");
+        out
+    }
+}
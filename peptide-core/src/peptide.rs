@@ -3,37 +3,29 @@ use std::{
     hash::{Hash, Hasher},
 };
 
-use engine::{
-    drawable::{Anchor, Drawable, sprite::Sprite},
-    exports::nalgebra::Vector2,
-    graphics_context::GraphicsContext,
-};
-use rand::{
-    rng,
-    seq::{IndexedRandom, IteratorRandom},
-};
-use serde::Deserialize;
+use nalgebra::Vector2;
+use rand::{Rng, seq::IndexedRandom};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    assets::{CONNECTOR_H, CONNECTOR_V, INTERACTION_H, INTERACTION_V},
-    game::{
-        amino::{Amino, AminoType},
-        level::Level,
-        world_to_screen,
-    },
-    misc::direction::{Direction, Directions},
+    amino::{Amino, AminoType, Interaction},
+    direction::{Direction, Directions},
+    generator::GeneratorConfig,
+    level::Level,
+    transform,
 };
 
-const CONNECTOR_OFFSETS: [Vector2<f32>; 4] = [
-    Vector2::new(0.0, 6.5),
-    Vector2::new(0.0, -5.5),
-    Vector2::new(-6.0, 0.0),
-    Vector2::new(6.0, 0.0),
-];
-
 const PLACEMENT_ORDER: [Direction; 3] = [Direction::Right, Direction::Up, Direction::Down];
 
-#[derive(Deserialize, PartialEq, Eq, Clone)]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Energy {
+    pub intrinsic: f32,
+    pub adjacency: f32,
+    pub hydrophobic: f32,
+    pub electrostatic: f32,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone)]
 pub struct Peptide {
     pub inner: HashMap<Vector2<i32>, Amino>,
 }
@@ -47,7 +39,9 @@ impl Peptide {
 
     pub fn for_level(level: &Level) -> Self {
         let mut inner = HashMap::new();
-        let mut amino = *level.peptide.inner.get(&Vector2::zeros()).unwrap();
+        let Some(mut amino) = level.peptide.get(Vector2::zeros()).copied() else {
+            return Self::empty();
+        };
         amino.children = Directions::empty();
         inner.insert(Vector2::zeros(), amino);
         Self { inner }
@@ -190,35 +184,52 @@ impl Peptide {
         None
     }
 
+    // depth first from the root, visiting children in the order each residue
+    // lists them
+    pub fn tree_order(&self) -> Vec<Vector2<i32>> {
+        let mut out = Vec::new();
+        let mut stack = vec![Vector2::zeros()];
+        while let Some(pos) = stack.pop() {
+            let Some(amino) = self.get(pos) else {
+                continue;
+            };
+
+            out.push(pos);
+            let children = amino.children.iter().collect::<Vec<_>>();
+            stack.extend(children.iter().rev().map(|dir| pos + dir.delta()));
+        }
+
+        out
+    }
+
     pub fn score(&self) -> f32 {
-        let mut energy = 0.0;
+        self.energy().total()
+    }
+
+    pub fn energy(&self) -> Energy {
+        let mut energy = Energy::default();
 
         for (pos, amino) in &self.inner {
-            energy += amino.amino.intrinsic_cost() as f32;
+            energy.intrinsic += amino.amino.intrinsic_cost() as f32;
 
-            let mut covered_sides = 0;
             for dir in Direction::ALL {
                 let Some(neighbor) = self.get(pos + dir.delta()) else {
                     continue;
                 };
 
-                covered_sides += 1;
                 if !neighbor.children.contains(dir.opposite()) && !amino.children.contains(dir) {
                     let adjacency = amino.amino.adjacency();
                     if let Some((_, bouns)) = adjacency.iter().find(|x| x.0 == neighbor.amino) {
-                        energy += *bouns as f32 / 2.0;
+                        energy.adjacency += *bouns as f32 / 2.0;
                     }
                 }
             }
 
-            // percent of non-bonded sides with neighbors
-            let bonds = amino.children.count() + self.parent(*pos).is_some() as u8;
-            if bonds < 4 {
-                let percent_cover = (covered_sides - bonds) as f32 / (4 - bonds) as f32;
+            if let Some(percent_cover) = self.percent_cover(*pos) {
                 if amino.amino.hydrophobic() > 0 {
-                    energy -= amino.amino.hydrophobic() as f32 * percent_cover;
+                    energy.hydrophobic -= amino.amino.hydrophobic() as f32 * percent_cover;
                 } else {
-                    energy += amino.amino.hydrophobic() as f32 * (1.0 - percent_cover);
+                    energy.hydrophobic += amino.amino.hydrophobic() as f32 * (1.0 - percent_cover);
                 }
             }
 
@@ -230,18 +241,43 @@ impl Peptide {
 
                 let delta = pos - pos_b;
                 let distance = delta.x.abs() + delta.y.abs();
-                energy += (amino.amino.charge() * amino_b.amino.charge()) as f32 / distance as f32;
+                energy.electrostatic +=
+                    (amino.amino.charge() * amino_b.amino.charge()) as f32 / distance as f32;
             }
         }
 
         energy
     }
 
-    pub fn mutate(&mut self) {
-        let mut rng = rng();
+    pub fn percent_cover(&self, pos: Vector2<i32>) -> Option<f32> {
+        let amino = self.get(pos)?;
+        let covered_sides = (Direction::ALL.iter())
+            .filter(|dir| self.get(pos + dir.delta()).is_some())
+            .count() as u8;
+
+        let bonds = amino.children.count() + self.parent(pos).is_some() as u8;
+        (bonds < 4).then(|| (covered_sides - bonds) as f32 / (4 - bonds) as f32)
+    }
+
+    // attaches a new residue, returning where it was placed. grows from `tip`
+    // unless the config's branching factor picks another residue. the config
+    // has to pass `GeneratorConfig::validate`
+    pub(crate) fn mutate(
+        &mut self,
+        rng: &mut impl Rng,
+        config: &GeneratorConfig,
+        tip: Vector2<i32>,
+    ) -> Vector2<i32> {
+        // sorted so the same rng state always picks the same residue
+        let mut positions = self.inner.keys().copied().collect::<Vec<_>>();
+        positions.sort_by_key(|x| (x.x, x.y));
+
+        let mut pos = tip;
+        if rng.random::<f32>() < config.branching {
+            pos = *positions.choose(rng).unwrap();
+        }
 
-        'outer: loop {
-            let pos = *self.inner.keys().choose(&mut rng).unwrap();
+        loop {
             for dir in PLACEMENT_ORDER {
                 let next = pos + dir.delta();
                 if self.inner.contains_key(&next) {
@@ -249,7 +285,7 @@ impl Peptide {
                 }
 
                 // because of my greedy path logic
-                let amino = *AminoType::ALL.choose(&mut rng).unwrap();
+                let amino = config.amino(rng);
                 if (self.get(pos).unwrap().children.iter())
                     .any(|x| self.get(pos + x.delta()).unwrap().amino == amino)
                 {
@@ -263,84 +299,69 @@ impl Peptide {
 
                 self.inner.get_mut(&pos).unwrap().children.set(dir);
                 self.inner.insert(next, amino);
-                break 'outer;
+                return next;
             }
+
+            pos = *positions.choose(rng).unwrap();
         }
     }
 
-    pub fn render(
-        &self,
-        ctx: &mut GraphicsContext,
-        origin: Vector2<f32>,
-        interactions: bool,
-        callback: impl Fn(&Vector2<i32>, Sprite) -> Sprite,
-    ) -> Option<Vector2<i32>> {
-        let mut hover = None;
-        for (pos, amino) in self.inner.iter() {
-            let render_pos = world_to_screen(*pos);
-            let sprite = Sprite::new(amino.amino.asset())
-                .scale(Vector2::repeat(6.0))
-                .position(origin + render_pos, Anchor::Center);
-            let sprite = callback(pos, sprite);
-            sprite.is_hovered(ctx).then(|| hover = Some(*pos));
-            sprite.draw(ctx);
-
-            for dir in amino.children.iter() {
-                let connector_offset = CONNECTOR_OFFSETS[dir as usize] * 6.0;
-                Sprite::new([CONNECTOR_V, CONNECTOR_H][dir.horizontal() as usize])
-                    .scale(Vector2::repeat(6.0))
-                    .position(origin + render_pos + connector_offset, Anchor::Center)
-                    .z_index(2)
-                    .draw(ctx);
-            }
-
-            if !interactions {
-                continue;
-            }
-
-            for dir in Direction::ALL {
-                if amino.children.contains(dir) {
-                    continue;
-                }
-
-                let Some(neighbor) = self.get(pos + dir.delta()) else {
+    // non-bonded neighbors in tree order, each pair once with the second residue
+    // to the right of or above the first
+    pub fn contacts(&self) -> Vec<(Vector2<i32>, Vector2<i32>)> {
+        let mut out = Vec::new();
+        for pos in self.tree_order() {
+            let amino = self.get(pos).unwrap();
+            for dir in [Direction::Right, Direction::Up] {
+                let next = pos + dir.delta();
+                let Some(neighbor) = self.get(next) else {
                     continue;
                 };
 
-                if neighbor.children.contains(dir.opposite()) {
-                    continue;
-                }
-
-                let interactions = amino.amino.adjacency();
-                if let Some((_, _)) = interactions.iter().find(|x| x.0 == neighbor.amino) {
-                    let connector_offset = CONNECTOR_OFFSETS[dir as usize] * 6.0;
-                    Sprite::new([INTERACTION_V, INTERACTION_H][dir.horizontal() as usize])
-                        .scale(Vector2::repeat(6.0))
-                        .position(origin + render_pos + connector_offset, Anchor::Center)
-                        .z_index(2)
-                        .draw(ctx);
+                if !amino.children.contains(dir) && !neighbor.children.contains(dir.opposite()) {
+                    out.push((pos, next));
                 }
             }
         }
 
-        hover
+        out
     }
 
-    pub fn offset_goal(&self) -> Vector2<f32> {
-        let (min, max) = self.bounds();
-        let center = (min.map(|c| c as f32) + max.map(|c| c as f32)) / 2.0;
-        let offset = center * 12.0 * 6.0;
+    pub fn interactions(&self) -> Vec<(Vector2<i32>, Vector2<i32>, Interaction)> {
+        (self.contacts().into_iter())
+            .filter_map(|(a, b)| {
+                let interaction = Interaction::between(self.get(a)?.amino, self.get(b)?.amino)?;
+                Some((a, b, interaction))
+            })
+            .collect()
+    }
+
+    // one of the eight lattice symmetries around the root
+    pub fn transformed(&self, symmetry: u8) -> Self {
+        let mut out = Self::empty();
+        for (pos, amino) in &self.inner {
+            let mut children = Directions::empty();
+            for dir in amino.children.iter() {
+                children.set(Direction::from_delta(transform(dir.delta(), symmetry)).unwrap());
+            }
+
+            let amino = Amino {
+                amino: amino.amino,
+                children,
+            };
+            out.inner.insert(transform(*pos, symmetry), amino);
+        }
 
-        -Vector2::new(offset.x, offset.y)
+        out
     }
 }
 
 pub macro peptide($($aa:ident at ($x:expr, $y:expr) $(-> ($($dir:ident),*))?),* $(,)?) {{
     use crate::{
-        game::amino::{Amino, AminoType},
-        misc::direction::{Direction, Directions},
+        amino::{Amino, AminoType},
+        direction::{Direction, Directions},
     };
-    use engine::exports::nalgebra::Vector2;
+    use nalgebra::Vector2;
     use std::collections::HashMap;
 
     let mut inner = HashMap::new();
@@ -355,7 +376,7 @@ pub macro peptide($($aa:ident at ($x:expr, $y:expr) $(-> ($($dir:ident),*))?),*
         );
     )*
 
-    crate::game::peptide::Peptide { inner }
+    crate::peptide::Peptide { inner }
 }}
 
 // its gross but it works...
@@ -366,3 +387,9 @@ impl Hash for Peptide {
         items.hash(state);
     }
 }
+
+impl Energy {
+    pub fn total(&self) -> f32 {
+        self.intrinsic + self.adjacency + self.hydrophobic + self.electrostatic
+    }
+}
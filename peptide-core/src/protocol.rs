@@ -0,0 +1,235 @@
+use std::io::{self, BufRead, Write};
+
+use nalgebra::Vector2;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    agent::Action,
+    amino::AminoType,
+    direction::{Direction, Directions},
+    generator::GeneratorConfig,
+    level::{LEVELS, Level, WIN_THRESHOLD},
+    peptide::Peptide,
+};
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Request {
+    // the current level, or campaign level `level`, or the level the game
+    // generates from `seed`
+    Reset {
+        #[serde(default)]
+        level: Option<usize>,
+        #[serde(default)]
+        seed: Option<u64>,
+    },
+    Observe,
+    // without `from` it's bonded to whichever neighbor is allowed to take it
+    Place {
+        amino: AminoType,
+        at: Vector2<i32>,
+        #[serde(default)]
+        from: Option<Vector2<i32>>,
+    },
+    Remove {
+        at: Vector2<i32>,
+    },
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Response {
+    Level {
+        title: String,
+        description: String,
+        range: (f32, f32),
+        residues: Vec<Residue>,
+    },
+    State {
+        residues: Vec<Residue>,
+        energy: f32,
+        score: f32,
+        options: Vec<Placement>,
+        solved: bool,
+        done: bool,
+        // why the request was rejected, in which case nothing changed
+        error: Option<String>,
+    },
+    Error {
+        message: String,
+    },
+}
+
+#[derive(Serialize)]
+pub struct Residue {
+    pub pos: Vector2<i32>,
+    pub amino: AminoType,
+    pub children: Directions,
+}
+
+#[derive(Serialize)]
+pub struct Placement {
+    pub amino: AminoType,
+    pub at: Vector2<i32>,
+    pub from: Vector2<i32>,
+}
+
+pub struct Session {
+    pub level: Level,
+    pub peptide: Peptide,
+}
+
+impl Session {
+    pub fn new(level: Level) -> Self {
+        Self {
+            peptide: Peptide::for_level(&level),
+            level,
+        }
+    }
+
+    pub fn serve(&mut self, input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+        for response in [self.describe(), self.observe(None)] {
+            send(&mut output, &response)?;
+        }
+
+        for line in input.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let responses = match serde_json::from_str(&line) {
+                Ok(request) => self.handle(request),
+                Err(error) => vec![Response::Error {
+                    message: error.to_string(),
+                }],
+            };
+            for response in &responses {
+                send(&mut output, response)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn handle(&mut self, request: Request) -> Vec<Response> {
+        let action = match request {
+            Request::Reset { level, seed } => return self.reset(level, seed),
+            Request::Observe => return vec![self.observe(None)],
+            Request::Place { amino, at, from } => self.placement(amino, at, from),
+            Request::Remove { at } => Ok(Action::Remove(at)),
+        };
+
+        let error = action.and_then(|x| {
+            let result = self.level.apply(&mut self.peptide, x);
+            result.map_err(|x| x.to_string())
+        });
+        vec![self.observe(error.err())]
+    }
+
+    fn reset(&mut self, level: Option<usize>, seed: Option<u64>) -> Vec<Response> {
+        let level = match (level, seed) {
+            (Some(_), Some(_)) => Err("reset takes either a level or a seed, not both".into()),
+            (Some(idx), None) => match LEVELS.get(idx) {
+                Some(level) => level.clone().map_err(|x| x.to_string()),
+                None => Err(format!("there are only {} levels", LEVELS.len())),
+            },
+            (None, Some(seed)) => {
+                let config = GeneratorConfig::for_seed(seed);
+                Level::generate_with(&config, seed).map_err(|x| x.to_string())
+            }
+            (None, None) => Ok(self.level.clone()),
+        };
+
+        match level {
+            Ok(level) => {
+                *self = Self::new(level);
+                vec![self.describe(), self.observe(None)]
+            }
+            Err(error) => vec![self.observe(Some(error))],
+        }
+    }
+
+    // works out which residue a placement is bonded to
+    fn placement(
+        &self,
+        amino: AminoType,
+        at: Vector2<i32>,
+        from: Option<Vector2<i32>>,
+    ) -> Result<Action, String> {
+        let parent = match from {
+            Some(from) => from,
+            None => (Direction::ALL.into_iter())
+                .map(|dir| at + dir.delta())
+                .find(|x| {
+                    self.peptide.inner.contains_key(x)
+                        && self.level.can_place(&self.peptide, *x, amino)
+                })
+                .ok_or(format!(
+                    "nothing next to ({}, {}) takes {amino:?}",
+                    at.x, at.y
+                ))?,
+        };
+
+        let dir = Direction::from_delta(at - parent).ok_or(format!(
+            "({}, {}) isn't next to ({}, {})",
+            at.x, at.y, parent.x, parent.y
+        ))?;
+        Ok(Action::Place { parent, dir, amino })
+    }
+
+    fn describe(&self) -> Response {
+        Response::Level {
+            title: self.level.title.clone(),
+            description: self.level.description.clone(),
+            range: self.level.range,
+            residues: residues(&self.level.peptide),
+        }
+    }
+
+    fn observe(&self, error: Option<String>) -> Response {
+        let energy = self.peptide.score();
+        let score = self.level.score(energy);
+        let done = self.peptide.inner.len() == self.level.peptide.inner.len();
+
+        let options = (self.level.placements(&self.peptide).into_iter())
+            .filter_map(|x| match x {
+                Action::Place { parent, dir, amino } => Some(Placement {
+                    amino,
+                    at: parent + dir.delta(),
+                    from: parent,
+                }),
+                Action::Remove(_) => None,
+            })
+            .collect();
+
+        Response::State {
+            residues: residues(&self.peptide),
+            energy,
+            score,
+            options,
+            solved: done && score >= WIN_THRESHOLD,
+            done,
+            error,
+        }
+    }
+}
+
+fn residues(peptide: &Peptide) -> Vec<Residue> {
+    (peptide.tree_order().into_iter())
+        .map(|pos| {
+            let amino = peptide.get(pos).unwrap();
+            Residue {
+                pos,
+                amino: amino.amino,
+                children: amino.children,
+            }
+        })
+        .collect()
+}
+
+fn send(output: &mut impl Write, response: &Response) -> io::Result<()> {
+    serde_json::to_writer(&mut *output, response)?;
+    writeln!(output)?;
+    output.flush()
+}
@@ -0,0 +1,326 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{self, Display},
+    iter::{Enumerate, Peekable},
+    str::Chars,
+};
+
+use nalgebra::Vector2;
+use serde::{
+    Deserialize, Deserializer,
+    de::{Error, MapAccess, Visitor, value::MapAccessDeserializer},
+};
+
+use crate::{
+    amino::{Amino, AminoType},
+    direction::{Direction, Directions},
+    peptide::Peptide,
+};
+
+// chains grow to the right and branches off of them up or down, like the
+// bundled levels are laid out
+const CHAIN_ORDER: [Direction; 4] = [
+    Direction::Right,
+    Direction::Up,
+    Direction::Down,
+    Direction::Left,
+];
+const BRANCH_ORDER: [Direction; 4] = [
+    Direction::Up,
+    Direction::Down,
+    Direction::Right,
+    Direction::Left,
+];
+
+// gives up on laying out peptides that are too crowded after this many tries
+const LAYOUT_BUDGET: u32 = 100_000;
+
+// positions are character offsets into the sequence
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SequenceError {
+    Empty,
+    UnknownResidue(usize, char),
+    MisplacedBranch(usize),
+    EmptyBranch(usize),
+    UnclosedBranch(usize),
+    UnmatchedParen(usize),
+    TooManyChildren(usize),
+    DuplicateSiblings(usize, AminoType),
+    NoLayout,
+}
+
+pub(super) struct Node {
+    pub amino: AminoType,
+    pub at: usize,
+    pub children: Vec<Node>,
+}
+
+struct Parser<'a> {
+    chars: Peekable<Enumerate<Chars<'a>>>,
+}
+
+impl Peptide {
+    // like `RF(L)DD(A)(C(F)LR)(DF)`, each residue's letter followed by its
+    // branches in parentheses and then the rest of its chain
+    pub fn from_sequence(sequence: &str) -> Result<Self, SequenceError> {
+        let root = parse(sequence)?;
+
+        let mut flat = Vec::new();
+        root.flatten(None, &CHAIN_ORDER, &mut flat);
+
+        let mut positions = vec![Vector2::zeros()];
+        let mut occupied = HashSet::from([Vector2::zeros()]);
+        let mut budget = LAYOUT_BUDGET;
+        if !layout(&flat, &mut positions, &mut occupied, &mut budget) {
+            return Err(SequenceError::NoLayout);
+        }
+
+        let mut inner = HashMap::new();
+        for (i, (amino, parent, _)) in flat.iter().enumerate() {
+            inner.insert(
+                positions[i],
+                Amino {
+                    amino: *amino,
+                    children: Directions::empty(),
+                },
+            );
+
+            if let Some(parent) = parent {
+                let dir = Direction::from_delta(positions[i] - positions[*parent]).unwrap();
+                inner
+                    .get_mut(&positions[*parent])
+                    .unwrap()
+                    .children
+                    .set(dir);
+            }
+        }
+
+        Ok(Self { inner })
+    }
+
+    // parsing the result gives back the same tree, though not necessarily the
+    // same layout
+    pub fn to_sequence(&self) -> String {
+        let mut out = String::new();
+        if self.get(Vector2::zeros()).is_some() {
+            self.write_sequence(Vector2::zeros(), &mut out);
+        }
+        out
+    }
+
+    fn write_sequence(&self, pos: Vector2<i32>, out: &mut String) {
+        let amino = self.get(pos).unwrap();
+        out.push(amino.amino.letter());
+
+        let chain = CHAIN_ORDER
+            .into_iter()
+            .find(|x| amino.children.contains(*x));
+        let branches = BRANCH_ORDER
+            .into_iter()
+            .filter(|x| amino.children.contains(*x) && Some(*x) != chain);
+
+        for dir in branches {
+            out.push('(');
+            self.write_sequence(pos + dir.delta(), out);
+            out.push(')');
+        }
+
+        if let Some(dir) = chain {
+            self.write_sequence(pos + dir.delta(), out);
+        }
+    }
+}
+
+pub(super) fn parse(sequence: &str) -> Result<Node, SequenceError> {
+    let mut parser = Parser {
+        chars: sequence.chars().enumerate().peekable(),
+    };
+
+    let root = match parser.peek() {
+        Some((_, '(' | ')')) | None => None,
+        Some(_) => Some(parser.chain()?),
+    };
+
+    match parser.peek() {
+        Some((at, '(')) => return Err(SequenceError::MisplacedBranch(at)),
+        Some((at, ')')) => return Err(SequenceError::UnmatchedParen(at)),
+        _ => {}
+    }
+
+    let root = root.ok_or(SequenceError::Empty)?;
+    root.check(true)?;
+    Ok(root)
+}
+
+impl Parser<'_> {
+    fn peek(&mut self) -> Option<(usize, char)> {
+        while self.chars.next_if(|x| x.1.is_whitespace()).is_some() {}
+        self.chars.peek().copied()
+    }
+
+    // a residue, its branches and then the rest of the chain
+    fn chain(&mut self) -> Result<Node, SequenceError> {
+        let (at, letter) = self.chars.next().unwrap();
+        let amino =
+            AminoType::from_letter(letter).ok_or(SequenceError::UnknownResidue(at, letter))?;
+        let mut node = Node {
+            amino,
+            at,
+            children: Vec::new(),
+        };
+
+        while let Some((open, '(')) = self.peek() {
+            self.chars.next();
+            match self.peek() {
+                Some((at, ')')) => return Err(SequenceError::EmptyBranch(at)),
+                Some((at, '(')) => return Err(SequenceError::MisplacedBranch(at)),
+                None => return Err(SequenceError::UnclosedBranch(open)),
+                Some(_) => node.children.push(self.chain()?),
+            }
+
+            if self.chars.next_if(|x| x.1 == ')').is_none() {
+                return Err(SequenceError::UnclosedBranch(open));
+            }
+        }
+
+        if let Some((_, chr)) = self.peek()
+            && chr != ')'
+        {
+            node.children.push(self.chain()?);
+        }
+
+        Ok(node)
+    }
+}
+
+impl Node {
+    fn check(&self, root: bool) -> Result<(), SequenceError> {
+        let free = if root { 4 } else { 3 };
+        if self.children.len() > free {
+            return Err(SequenceError::TooManyChildren(self.at));
+        }
+
+        let mut seen = HashSet::new();
+        for child in &self.children {
+            if !seen.insert(child.amino) {
+                return Err(SequenceError::DuplicateSiblings(child.at, child.amino));
+            }
+            child.check(false)?;
+        }
+
+        Ok(())
+    }
+
+    // preorder list of each residue, its parent's index and the directions
+    // to try placing it in
+    fn flatten(
+        &self,
+        parent: Option<usize>,
+        order: &'static [Direction; 4],
+        out: &mut Vec<(AminoType, Option<usize>, &'static [Direction; 4])>,
+    ) {
+        let idx = out.len();
+        out.push((self.amino, parent, order));
+
+        // a residue without a chain after it just lays its last branch out
+        // like one
+        let chain = self.children.len().saturating_sub(1);
+        for (i, child) in self.children.iter().enumerate() {
+            let order = if i == chain {
+                &CHAIN_ORDER
+            } else {
+                &BRANCH_ORDER
+            };
+            child.flatten(Some(idx), order, out);
+        }
+    }
+}
+
+// places each residue next to its parent, backtracking when one gets boxed in
+fn layout(
+    flat: &[(AminoType, Option<usize>, &[Direction; 4])],
+    positions: &mut Vec<Vector2<i32>>,
+    occupied: &mut HashSet<Vector2<i32>>,
+    budget: &mut u32,
+) -> bool {
+    let Some((_, Some(parent), order)) = flat.get(positions.len()) else {
+        return true;
+    };
+
+    for dir in order.iter() {
+        let pos = positions[*parent] + dir.delta();
+        if *budget == 0 || !occupied.insert(pos) {
+            continue;
+        }
+
+        *budget -= 1;
+        positions.push(pos);
+        if layout(flat, positions, occupied, budget) {
+            return true;
+        }
+
+        positions.pop();
+        occupied.remove(&pos);
+    }
+
+    false
+}
+
+// lets level files give their peptide as a sequence instead of listing
+// every residue
+pub fn parse_peptide<'de, D>(from: D) -> Result<Peptide, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct PeptideVisitor;
+
+    impl<'de> Visitor<'de> for PeptideVisitor {
+        type Value = Peptide;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a peptide or a sequence")
+        }
+
+        fn visit_str<E: Error>(self, sequence: &str) -> Result<Peptide, E> {
+            Peptide::from_sequence(sequence).map_err(E::custom)
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Peptide, A::Error> {
+            Peptide::deserialize(MapAccessDeserializer::new(map))
+        }
+    }
+
+    from.deserialize_any(PeptideVisitor)
+}
+
+impl Display for SequenceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SequenceError::Empty => f.write_str("sequence is empty"),
+            SequenceError::UnknownResidue(at, chr) => write!(
+                f,
+                "column {}: '{chr}' is not one of A, C, D, F, L or R",
+                at + 1
+            ),
+            SequenceError::MisplacedBranch(at) => {
+                write!(f, "column {}: branch doesn't follow a residue", at + 1)
+            }
+            SequenceError::EmptyBranch(at) => write!(f, "column {}: branch is empty", at + 1),
+            SequenceError::UnclosedBranch(at) => {
+                write!(f, "column {}: branch is never closed", at + 1)
+            }
+            SequenceError::UnmatchedParen(at) => {
+                write!(f, "column {}: ')' doesn't close a branch", at + 1)
+            }
+            SequenceError::TooManyChildren(at) => {
+                write!(f, "column {}: residue has too many branches", at + 1)
+            }
+            SequenceError::DuplicateSiblings(at, amino) => write!(
+                f,
+                "column {}: residue already has a {amino:?} child",
+                at + 1
+            ),
+            SequenceError::NoLayout => f.write_str("residues don't fit on the lattice"),
+        }
+    }
+}
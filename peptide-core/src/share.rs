@@ -0,0 +1,162 @@
+use std::{
+    fmt::{self, Display},
+    io::{self, Read, Write},
+};
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use flate2::{Compression, read::DeflateDecoder, write::DeflateEncoder};
+use serde::{Deserialize, Serialize};
+
+use crate::{fold::FoldError, level::Level, peptide::Peptide, validate::Violation};
+
+#[derive(Clone)]
+pub enum ShareCode {
+    Seed { seed: u64 },
+    // its range is left unsolved, as solving can take a while
+    Level(Level),
+    // `level` is the level's fingerprint
+    Solution { level: u64, fold: String },
+}
+
+#[derive(Debug)]
+pub enum ShareError {
+    Encoding(base64::DecodeError),
+    Compression(io::Error),
+    Format(ron::Error),
+    Fold(FoldError),
+    Invalid(Vec<Violation>),
+    WrongLevel,
+    NotASolution,
+}
+
+// what actually goes in the code, with peptides written as folds to keep
+// it short
+#[derive(Serialize, Deserialize)]
+enum Payload {
+    Seed {
+        seed: u64,
+    },
+    Level {
+        title: String,
+        description: String,
+        range: (f32, f32),
+        seed: Option<u64>,
+        fold: String,
+    },
+    Solution {
+        level: u64,
+        fold: String,
+    },
+}
+
+impl ShareCode {
+    pub fn encode(&self) -> String {
+        let payload = match self {
+            ShareCode::Seed { seed } => Payload::Seed { seed: *seed },
+            ShareCode::Level(level) => Payload::Level {
+                title: level.title.clone(),
+                description: level.description.clone(),
+                range: level.range,
+                seed: level.seed,
+                fold: level.peptide.to_fold(),
+            },
+            ShareCode::Solution { level, fold } => Payload::Solution {
+                level: *level,
+                fold: fold.clone(),
+            },
+        };
+
+        let raw = ron::to_string(&payload).unwrap();
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::best());
+        encoder.write_all(raw.as_bytes()).unwrap();
+        URL_SAFE_NO_PAD.encode(encoder.finish().unwrap())
+    }
+
+    pub fn decode(code: &str) -> Result<Self, ShareError> {
+        let compressed = URL_SAFE_NO_PAD
+            .decode(code.trim())
+            .map_err(ShareError::Encoding)?;
+        let mut raw = Vec::new();
+        (DeflateDecoder::new(&compressed[..]).read_to_end(&mut raw))
+            .map_err(ShareError::Compression)?;
+        let payload = ron::de::from_bytes(&raw).map_err(|x| ShareError::Format(x.code))?;
+
+        Ok(match payload {
+            Payload::Seed { seed } => ShareCode::Seed { seed },
+            Payload::Level {
+                title,
+                description,
+                seed,
+                fold,
+                ..
+            } => {
+                let peptide = Peptide::from_fold(&fold).map_err(ShareError::Fold)?;
+                let violations = peptide.validate();
+                if !violations.is_empty() {
+                    return Err(ShareError::Invalid(violations));
+                }
+
+                // the range decides which folds win, so it's never taken from
+                // the code
+                ShareCode::Level(Level {
+                    title,
+                    description,
+                    range: (f32::NAN, f32::NAN),
+                    seed,
+                    peptide,
+                })
+            }
+            Payload::Solution { level, fold } => ShareCode::Solution { level, fold },
+        })
+    }
+}
+
+impl Level {
+    // stable between versions so old solution codes keep working
+    pub fn fingerprint(&self) -> u64 {
+        let fold = self.peptide.to_fold();
+        let sequence = fold.split_once('/').unwrap().0;
+
+        // FNV-1a
+        let mut hash = 0xcbf2_9ce4_8422_2325_u64;
+        for byte in sequence.bytes() {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+        hash
+    }
+
+    pub fn solution_code(&self, peptide: &Peptide) -> String {
+        ShareCode::Solution {
+            level: self.fingerprint(),
+            fold: peptide.to_fold(),
+        }
+        .encode()
+    }
+
+    pub fn decode_solution(&self, code: &str) -> Result<Peptide, ShareError> {
+        let ShareCode::Solution { level, fold } = ShareCode::decode(code)? else {
+            return Err(ShareError::NotASolution);
+        };
+
+        if level != self.fingerprint() {
+            return Err(ShareError::WrongLevel);
+        }
+
+        self.decode_fold(&fold).map_err(ShareError::Fold)
+    }
+}
+
+impl Display for ShareError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShareError::Encoding(_) | ShareError::Compression(_) | ShareError::Format(_) => {
+                f.write_str("not a valid code")
+            }
+            ShareError::Fold(error) => write!(f, "invalid fold, {error}"),
+            ShareError::Invalid(violations) => write!(f, "invalid level, {}", violations[0]),
+            ShareError::WrongLevel => f.write_str("solution is for a different level"),
+            ShareError::NotASolution => f.write_str("code is not a solution"),
+        }
+    }
+}
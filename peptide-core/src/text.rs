@@ -0,0 +1,186 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use nalgebra::Vector2;
+
+use crate::{
+    amino::{Amino, AminoType},
+    direction::{Direction, Directions},
+    peptide::Peptide,
+    validate::Violation,
+};
+
+// lines and columns start at one
+#[derive(Debug, Clone)]
+pub enum TextError {
+    MissingRoot,
+    MultipleRoots(usize, usize),
+    UnexpectedChar(usize, usize, char),
+    DanglingBond(usize, usize),
+    Cycle(usize, usize),
+    Unreachable(usize, usize),
+    Invalid(Vec<Violation>),
+}
+
+impl Display for Peptide {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.inner.is_empty() {
+            return Ok(());
+        }
+
+        let (min, max) = self.bounds();
+        let size = (max - min).map(|x| x as usize * 2 + 1);
+        let mut grid = vec![vec![' '; size.x]; size.y];
+
+        for (pos, amino) in &self.inner {
+            let (col, row) = ((pos.x - min.x) as usize * 2, (max.y - pos.y) as usize * 2);
+            grid[row][col] = match *pos == Vector2::zeros() {
+                true => amino.amino.letter().to_ascii_lowercase(),
+                false => amino.amino.letter(),
+            };
+
+            for dir in [Direction::Right, Direction::Down] {
+                let (col, row) = match dir {
+                    Direction::Right => (col + 1, row),
+                    _ => (col, row + 1),
+                };
+
+                let bonded = amino.children.contains(dir)
+                    || (self.get(pos + dir.delta()))
+                        .is_some_and(|x| x.children.contains(dir.opposite()));
+                if bonded {
+                    grid[row][col] = if dir.horizontal() { '-' } else { '|' };
+                }
+            }
+        }
+
+        // halfway between the two residues
+        for (a, b, _) in self.interactions() {
+            let (col, row) = (
+                (a.x + b.x - min.x * 2) as usize,
+                (max.y * 2 - a.y - b.y) as usize,
+            );
+            grid[row][col] = if a.y == b.y { '=' } else { ':' };
+        }
+
+        let lines = grid.iter().map(|x| x.iter().collect::<String>());
+        let lines = lines.map(|x| x.trim_end().to_owned()).collect::<Vec<_>>();
+        f.write_str(&lines.join("\n"))
+    }
+}
+
+impl FromStr for Peptide {
+    type Err = TextError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let chars = text
+            .lines()
+            .enumerate()
+            .flat_map(|(row, line)| line.chars().enumerate().map(move |(col, x)| (row, col, x)))
+            .filter(|x| x.2 != ' ')
+            .collect::<Vec<_>>();
+
+        let mut roots = chars.iter().filter(|x| x.2.is_ascii_lowercase());
+        let &(root_row, root_col, _) = roots.next().ok_or(TextError::MissingRoot)?;
+        if let Some((row, col, _)) = roots.next() {
+            return Err(TextError::MultipleRoots(row + 1, col + 1));
+        }
+
+        // residues keep where they were in the text for errors
+        let mut residues = HashMap::new();
+        let mut bonds = Vec::new();
+        for &(row, col, chr) in &chars {
+            let (dx, dy) = (col as i32 - root_col as i32, root_row as i32 - row as i32);
+            let unexpected = TextError::UnexpectedChar(row + 1, col + 1, chr);
+            match (dx.rem_euclid(2), dy.rem_euclid(2), chr) {
+                (0, 0, _) => {
+                    let amino =
+                        AminoType::from_letter(chr.to_ascii_uppercase()).ok_or(unexpected)?;
+                    residues.insert(Vector2::new(dx, dy) / 2, (amino, (row + 1, col + 1)));
+                }
+                (1, 0, '-') => {
+                    bonds.push((Vector2::new(dx - 1, dy) / 2, Direction::Right, (row, col)))
+                }
+                (0, 1, '|') => {
+                    bonds.push((Vector2::new(dx, dy + 1) / 2, Direction::Down, (row, col)))
+                }
+                (1, 0, '=') | (0, 1, ':') => {}
+                _ => return Err(unexpected),
+            }
+        }
+
+        let mut neighbors = HashMap::<_, Vec<_>>::new();
+        for (pos, dir, (row, col)) in bonds {
+            let next = pos + dir.delta();
+            if !residues.contains_key(&pos) || !residues.contains_key(&next) {
+                return Err(TextError::DanglingBond(row + 1, col + 1));
+            }
+
+            neighbors.entry(pos).or_default().push(dir);
+            neighbors.entry(next).or_default().push(dir.opposite());
+        }
+
+        let mut inner = HashMap::new();
+        let mut queue = VecDeque::from([(Vector2::zeros(), None)]);
+        while let Some((pos, parent)) = queue.pop_front() {
+            let (amino, (row, col)) = residues[&pos];
+            if inner.contains_key(&pos) {
+                return Err(TextError::Cycle(row, col));
+            }
+
+            let mut children = Directions::empty();
+            for dir in neighbors.get(&pos).into_iter().flatten() {
+                if Some(dir.opposite()) != parent {
+                    children.set(*dir);
+                    queue.push_back((pos + dir.delta(), Some(*dir)));
+                }
+            }
+
+            inner.insert(pos, Amino { amino, children });
+        }
+
+        let unreachable = residues.iter().filter(|x| !inner.contains_key(x.0));
+        if let Some((row, col)) = unreachable.map(|x| x.1.1).min() {
+            return Err(TextError::Unreachable(row, col));
+        }
+
+        let peptide = Self { inner };
+        let violations = peptide.validate();
+        if !violations.is_empty() {
+            return Err(TextError::Invalid(violations));
+        }
+
+        Ok(peptide)
+    }
+}
+
+impl Display for TextError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TextError::MissingRoot => f.write_str("no lowercase root residue"),
+            TextError::MultipleRoots(row, col) => {
+                write!(f, "{row}:{col}: only the root residue can be lowercase")
+            }
+            TextError::UnexpectedChar(row, col, chr) => {
+                write!(f, "{row}:{col}: unexpected '{chr}'")
+            }
+            TextError::DanglingBond(row, col) => {
+                write!(f, "{row}:{col}: bond doesn't connect two residues")
+            }
+            TextError::Cycle(row, col) => write!(f, "{row}:{col}: residue is bonded in a loop"),
+            TextError::Unreachable(row, col) => {
+                write!(f, "{row}:{col}: residue isn't bonded to the root")
+            }
+            TextError::Invalid(violations) => {
+                f.write_str("peptide is invalid:")?;
+                for violation in violations {
+                    write!(f, "\n ∙ {violation}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt::{self, Display},
+};
+
+use nalgebra::Vector2;
+
+use crate::{amino::AminoType, direction::Direction, peptide::Peptide};
+
+#[derive(Debug, Clone)]
+pub enum Violation {
+    MissingRoot,
+    DanglingChild(Vector2<i32>, Direction),
+    RootHasParent(Vector2<i32>),
+    MultipleParents(Vector2<i32>),
+    Unreachable(Vector2<i32>),
+    // makes `Peptide::find` ambiguous
+    DuplicateSiblings(Vector2<i32>, AminoType),
+}
+
+impl Violation {
+    pub fn pos(&self) -> Option<Vector2<i32>> {
+        match self {
+            Violation::MissingRoot => None,
+            Violation::DanglingChild(pos, _)
+            | Violation::RootHasParent(pos)
+            | Violation::MultipleParents(pos)
+            | Violation::Unreachable(pos)
+            | Violation::DuplicateSiblings(pos, _) => Some(*pos),
+        }
+    }
+}
+
+impl Peptide {
+    // sorted by position after a missing root
+    pub fn validate(&self) -> Vec<Violation> {
+        let mut out = Vec::new();
+        let by_position = |x: &Violation| x.pos().map(|x| (x.x, x.y));
+
+        let mut positions = self.inner.keys().copied().collect::<Vec<_>>();
+        positions.sort_by_key(|x| (x.x, x.y));
+
+        let mut parents = HashMap::<_, u32>::new();
+        for pos in &positions {
+            let amino = self.get(*pos).unwrap();
+
+            let mut seen = HashSet::new();
+            for dir in amino.children.iter() {
+                let child = pos + dir.delta();
+                let Some(next) = self.get(child) else {
+                    out.push(Violation::DanglingChild(*pos, dir));
+                    continue;
+                };
+
+                *parents.entry(child).or_default() += 1;
+                if !seen.insert(next.amino) {
+                    out.push(Violation::DuplicateSiblings(*pos, next.amino));
+                }
+            }
+        }
+
+        if self.get(Vector2::zeros()).is_none() {
+            out.sort_by_key(by_position);
+            out.insert(0, Violation::MissingRoot);
+            return out;
+        }
+
+        for pos in &positions {
+            let count = parents.get(pos).copied().unwrap_or_default();
+            if *pos == Vector2::zeros() && count > 0 {
+                out.push(Violation::RootHasParent(*pos));
+            } else if count > 1 {
+                out.push(Violation::MultipleParents(*pos));
+            }
+        }
+
+        let mut reachable = HashSet::new();
+        let mut queue = VecDeque::from([Vector2::zeros()]);
+        while let Some(pos) = queue.pop_front() {
+            let Some(amino) = self.get(pos) else {
+                continue;
+            };
+
+            if reachable.insert(pos) {
+                queue.extend(amino.children.iter().map(|dir| pos + dir.delta()));
+            }
+        }
+
+        (positions.iter())
+            .filter(|x| !reachable.contains(*x))
+            .for_each(|x| out.push(Violation::Unreachable(*x)));
+
+        out.sort_by_key(by_position);
+        out
+    }
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pos = self.pos().unwrap_or_default();
+        let (x, y) = (pos.x, pos.y);
+        match self {
+            Violation::MissingRoot => f.write_str("no residue at (0, 0)"),
+            Violation::DanglingChild(_, dir) => write!(
+                f,
+                "residue at ({x}, {y}) has a child to the {dir:?}, but that cell is empty"
+            ),
+            Violation::RootHasParent(_) => f.write_str("the root is another residue's child"),
+            Violation::MultipleParents(_) => {
+                write!(f, "residue at ({x}, {y}) has multiple parents")
+            }
+            Violation::Unreachable(_) => {
+                write!(f, "residue at ({x}, {y}) isn't connected to the root")
+            }
+            Violation::DuplicateSiblings(_, amino) => {
+                write!(f, "residue at ({x}, {y}) has multiple {amino:?} children")
+            }
+        }
+    }
+}
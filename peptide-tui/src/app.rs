@@ -0,0 +1,134 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use nalgebra::Vector2;
+use peptide_core::{
+    amino::Amino,
+    direction::{Direction, Directions},
+    error::{LevelError, playable},
+    level::{Level, WIN_THRESHOLD},
+    peptide::Peptide,
+};
+
+pub struct App {
+    pub levels: Vec<Result<Level, LevelError>>,
+    pub level_idx: usize,
+    pub unlocked: usize,
+    pub level: Level,
+
+    pub peptide: Peptide,
+    pub cursor: Vector2<i32>,
+    pub selected: Option<Vector2<i32>>,
+    pub child_idx: u8,
+    pub quit: bool,
+}
+
+impl App {
+    pub fn new(levels: Vec<Result<Level, LevelError>>) -> Self {
+        let level = playable(&levels[0]).into_owned();
+        Self {
+            peptide: Peptide::for_level(&level),
+            levels,
+            level_idx: 0,
+            unlocked: 0,
+            level,
+
+            cursor: Vector2::zeros(),
+            selected: None,
+            child_idx: 0,
+            quit: false,
+        }
+    }
+
+    pub fn load_level(&mut self, idx: usize) {
+        if idx >= self.levels.len() || idx > self.unlocked {
+            return;
+        }
+
+        self.level_idx = idx;
+        self.level = playable(&self.levels[idx]).into_owned();
+        self.reset();
+    }
+
+    pub fn reset(&mut self) {
+        self.peptide = Peptide::for_level(&self.level);
+        self.cursor = Vector2::zeros();
+        self.selected = None;
+        self.child_idx = 0;
+    }
+
+    pub fn complete(&self) -> bool {
+        self.peptide.inner.len() == self.level.peptide.inner.len()
+    }
+
+    pub fn solved(&self) -> bool {
+        self.complete() && self.level.score(self.peptide.score()) >= WIN_THRESHOLD
+    }
+
+    // where the next residue goes, like the ghost in the game
+    pub fn next_child(&self) -> Option<Vector2<i32>> {
+        let selected = self.selected?;
+        (self.level).next_child(&self.peptide, selected, self.child_idx)
+    }
+
+    pub fn can_place(&self, pos: Vector2<i32>) -> bool {
+        let Some(selected) = self.selected else {
+            return false;
+        };
+
+        self.next_child().is_some()
+            && !self.peptide.inner.contains_key(&pos)
+            && Direction::from_delta(pos - selected).is_some()
+    }
+
+    pub fn key(&mut self, key: KeyEvent) {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Char('c') if ctrl => self.quit = true,
+            KeyCode::Char('q') => self.quit = true,
+
+            KeyCode::Up | KeyCode::Char('k') => self.cursor.y += 1,
+            KeyCode::Down | KeyCode::Char('j') => self.cursor.y -= 1,
+            KeyCode::Left | KeyCode::Char('h') => self.cursor.x -= 1,
+            KeyCode::Right | KeyCode::Char('l') => self.cursor.x += 1,
+
+            KeyCode::Tab => self.child_idx = (self.child_idx + 1) % 3,
+            KeyCode::BackTab => self.child_idx = (self.child_idx + 2) % 3,
+            KeyCode::Enter | KeyCode::Char(' ') => self.activate(),
+            KeyCode::Backspace | KeyCode::Delete | KeyCode::Char('x') => {
+                self.peptide.remove(self.cursor);
+                (self.selected).take_if(|x| !self.peptide.inner.contains_key(x));
+            }
+            KeyCode::Esc => self.selected = None,
+
+            KeyCode::Char('r') => self.reset(),
+            KeyCode::Char('n') => self.load_level(self.level_idx + 1),
+            KeyCode::Char('p') if self.level_idx > 0 => self.load_level(self.level_idx - 1),
+            _ => {}
+        }
+
+        if self.solved() {
+            self.unlocked = self.unlocked.max(self.level_idx + 1);
+        }
+    }
+
+    // selects the residue under the cursor, or places the next one there
+    fn activate(&mut self) {
+        if self.peptide.inner.contains_key(&self.cursor) {
+            self.selected = Some(self.cursor);
+            return;
+        }
+
+        if !self.can_place(self.cursor) {
+            return;
+        }
+
+        let (selected, next) = (self.selected.unwrap(), self.next_child().unwrap());
+        let dir = Direction::from_delta(self.cursor - selected).unwrap();
+        (self.peptide.inner.get_mut(&selected).unwrap().children).set(dir);
+        let amino = Amino {
+            amino: self.level.get(next).unwrap().amino,
+            children: Directions::empty(),
+        };
+        self.peptide.inner.insert(self.cursor, amino);
+        self.selected = Some(self.cursor);
+    }
+}
@@ -0,0 +1,229 @@
+use std::{
+    io::{self, Write},
+    mem,
+};
+
+use crossterm::{
+    cursor::MoveTo,
+    queue,
+    style::{Print, PrintStyledContent, Stylize},
+    terminal::{Clear, ClearType},
+};
+use nalgebra::Vector2;
+use peptide_core::{direction::Direction, level::WIN_THRESHOLD, peptide::Peptide};
+
+use crate::app::App;
+
+const HELP: &str = "arrows move · enter select/place · tab cycle · x remove · esc deselect · \
+                    r reset · n/p level · q quit";
+const DESCRIPTION_WIDTH: usize = 72;
+// columns between the target and the player's fold
+const GUTTER: u16 = 8;
+
+type Bounds = (Vector2<i32>, Vector2<i32>);
+
+pub fn draw(out: &mut impl Write, app: &App) -> io::Result<()> {
+    queue!(out, Clear(ClearType::All))?;
+
+    let title = format!(
+        "{} ({}/{})",
+        app.level.title,
+        app.level_idx + 1,
+        app.levels.len()
+    );
+    queue!(out, MoveTo(0, 0), PrintStyledContent(title.bold()))?;
+    let description = wrap(&app.level.description, DESCRIPTION_WIDTH);
+    for (i, text) in description.iter().enumerate() {
+        let text = text.as_str().dark_grey();
+        queue!(out, MoveTo(0, i as u16 + 1), PrintStyledContent(text))?;
+    }
+    let mut row = description.len() as u16 + 2;
+
+    // unavailable levels have nothing to fold
+    if app.level.peptide.inner.is_empty() {
+        return draw_status(out, app, row);
+    }
+
+    // room around the fold to place residues into, with the target padded to
+    // match so they line up
+    let (min, max) = app.level.peptide.bounds();
+    let target = (min - Vector2::repeat(1), max + Vector2::repeat(1));
+    let (min, max) = app.peptide.bounds();
+    let view = (
+        min.inf(&app.cursor) - Vector2::repeat(1),
+        max.sup(&app.cursor) + Vector2::repeat(1),
+    );
+
+    let fold_col = width(target) + GUTTER;
+    queue!(out, MoveTo(0, row), Print("Target".underlined()))?;
+    queue!(out, MoveTo(fold_col, row), Print("Fold".underlined()))?;
+    row += 2;
+
+    draw_target(out, app, (0, row), target)?;
+    draw_fold(out, app, (fold_col, row), view)?;
+    row += height(target).max(height(view)) + 1;
+
+    draw_status(out, app, row)
+}
+
+// the level's peptide, with residues already in the fold grayed out like in
+// the game
+fn draw_target(
+    out: &mut impl Write,
+    app: &App,
+    corner: (u16, u16),
+    view: Bounds,
+) -> io::Result<()> {
+    let level = &app.level.peptide;
+    draw_peptide(out, level, corner, view)?;
+
+    let selected = (app.selected).and_then(|x| level.find(&app.peptide.path(x)));
+    let next = app.next_child();
+    for pos in level.inner.keys() {
+        let letter = letter(level, *pos).to_string();
+        let styled = if Some(*pos) == selected {
+            letter.reverse()
+        } else if Some(*pos) == next {
+            letter.yellow().bold()
+        } else if app.peptide.find(&level.path(*pos)).is_some() {
+            letter.dark_grey()
+        } else {
+            continue;
+        };
+
+        let (x, y) = cell(corner, view, *pos);
+        queue!(out, MoveTo(x, y), PrintStyledContent(styled))?;
+    }
+
+    Ok(())
+}
+
+// the player's fold, with a ghost of the next residue wherever it could be
+// placed and the cursor on top
+fn draw_fold(out: &mut impl Write, app: &App, corner: (u16, u16), view: Bounds) -> io::Result<()> {
+    draw_peptide(out, &app.peptide, corner, view)?;
+
+    if let Some(selected) = app.selected {
+        let (x, y) = cell(corner, view, selected);
+        let letter = letter(&app.peptide, selected).to_string();
+        queue!(
+            out,
+            MoveTo(x, y),
+            PrintStyledContent(letter.yellow().bold())
+        )?;
+
+        for dir in Direction::ALL {
+            let pos = selected + dir.delta();
+            if app.can_place(pos) {
+                let (x, y) = cell(corner, view, pos);
+                queue!(out, MoveTo(x, y), PrintStyledContent("·".dark_grey()))?;
+            }
+        }
+    }
+
+    let (x, y) = cell(corner, view, app.cursor);
+    let under = match app.peptide.get(app.cursor) {
+        Some(_) => letter(&app.peptide, app.cursor).to_string().reverse(),
+        None if app.can_place(app.cursor) => {
+            let next = app.level.get(app.next_child().unwrap()).unwrap();
+            next.amino.letter().to_string().dark_grey().reverse()
+        }
+        None => " ".to_string().reverse(),
+    };
+    queue!(out, MoveTo(x, y), PrintStyledContent(under))
+}
+
+fn draw_status(out: &mut impl Write, app: &App, mut row: u16) -> io::Result<()> {
+    let energy = app.peptide.score();
+    let percent = (app.level.score(energy).clamp(0.0, 1.0) * 100.0).round() as u32;
+    let (built, total) = (app.peptide.inner.len(), app.level.peptide.inner.len());
+    let status = format!("Energy: {energy:.2}   Score: {percent}%   Residues: {built}/{total}");
+    queue!(out, MoveTo(0, row), Print(status))?;
+    row += 1;
+
+    let message = if app.solved() && app.level_idx + 1 == app.levels.len() {
+        "Every level is solved!".to_string().green().bold()
+    } else if app.solved() {
+        "Solved! Press n for the next level."
+            .to_string()
+            .green()
+            .bold()
+    } else if app.complete() {
+        format!("Reach {:.0}% to win.", WIN_THRESHOLD * 100.0).stylize()
+    } else if let Some(next) = app.next_child() {
+        let amino = app.level.get(next).unwrap().amino;
+        format!("Next: {} (tab to cycle)", amino.name().trim()).stylize()
+    } else {
+        "Select a residue to build from.".to_string().stylize()
+    };
+    queue!(out, MoveTo(0, row), PrintStyledContent(message))?;
+
+    queue!(
+        out,
+        MoveTo(0, row + 2),
+        PrintStyledContent(HELP.dark_grey())
+    )
+}
+
+// draws the peptide as text with the top left of `view` at `corner`
+fn draw_peptide(
+    out: &mut impl Write,
+    peptide: &Peptide,
+    corner: (u16, u16),
+    view: Bounds,
+) -> io::Result<()> {
+    let (min, max) = peptide.bounds();
+    let (x, y) = cell(corner, view, Vector2::new(min.x, max.y));
+    for (i, line) in peptide.to_string().lines().enumerate() {
+        // spaces would draw over whatever is next to the peptide
+        for (j, chr) in line.chars().enumerate().filter(|x| x.1 != ' ') {
+            queue!(out, MoveTo(x + j as u16, y + i as u16), Print(chr))?;
+        }
+    }
+
+    Ok(())
+}
+
+// lattice positions take up every other column and row, like in
+// `Display for Peptide`
+fn cell(corner: (u16, u16), view: Bounds, pos: Vector2<i32>) -> (u16, u16) {
+    let x = corner.0 + (pos.x - view.0.x) as u16 * 2;
+    let y = corner.1 + (view.1.y - pos.y) as u16 * 2;
+    (x, y)
+}
+
+fn width(view: Bounds) -> u16 {
+    (view.1.x - view.0.x) as u16 * 2 + 1
+}
+
+fn height(view: Bounds) -> u16 {
+    (view.1.y - view.0.y) as u16 * 2 + 1
+}
+
+fn letter(peptide: &Peptide, pos: Vector2<i32>) -> char {
+    let letter = peptide.get(pos).unwrap().amino.letter();
+    match pos == Vector2::zeros() {
+        true => letter.to_ascii_lowercase(),
+        false => letter,
+    }
+}
+
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut out = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            if !line.is_empty() && line.len() + word.len() + 1 > width {
+                out.push(mem::take(&mut line));
+            }
+
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+        out.push(line);
+    }
+
+    out
+}
@@ -0,0 +1,85 @@
+use std::{
+    env, fs,
+    io::{self, Write},
+    path::Path,
+    process,
+};
+
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyEventKind},
+    execute,
+    terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use peptide_core::{
+    error::LevelError,
+    level::{LEVELS, Level},
+};
+
+use crate::app::App;
+
+mod app;
+mod draw;
+
+fn main() {
+    // level files passed as arguments are played instead of the campaign
+    let files = env::args().skip(1).collect::<Vec<_>>();
+    let levels = if files.is_empty() {
+        LEVELS.clone()
+    } else {
+        files.iter().map(|x| load(Path::new(x))).collect()
+    };
+
+    let mut app = App::new(levels);
+    if let Err(error) = run(&mut app) {
+        eprintln!("{error}");
+        process::exit(1);
+    }
+}
+
+fn run(app: &mut App) -> io::Result<()> {
+    let _terminal = Terminal::enter()?;
+    let mut out = io::stdout();
+
+    while !app.quit {
+        draw::draw(&mut out, app)?;
+        out.flush()?;
+
+        if let Event::Key(key) = event::read()?
+            && key.kind != KeyEventKind::Release
+        {
+            app.key(key);
+        }
+    }
+
+    Ok(())
+}
+
+// broken levels are still played, as a placeholder explaining the problem
+fn load(path: &Path) -> Result<Level, LevelError> {
+    let raw = fs::read(path).unwrap_or_else(|error| {
+        eprintln!("{}: {error}", path.display());
+        process::exit(1);
+    });
+
+    let file = path.file_name().unwrap_or_default().to_string_lossy();
+    Level::load(&file, &raw)
+}
+
+// puts the terminal back how it was when dropped, even after a panic
+struct Terminal;
+
+impl Terminal {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), EnterAlternateScreen, cursor::Hide)?;
+        Ok(Self)
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), cursor::Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
@@ -4,6 +4,8 @@ use engine::{
 };
 use image::RgbaImage;
 
+use crate::game::{amino::AminoType, render::AminoSprite};
+
 define_refs! {
     FontRef => {
         UNDEAD_FONT
@@ -38,6 +40,31 @@ define_refs! {
     }
 }
 
+// where sprites are in tiles.png as their position and size, for the ones
+// `game::headless` draws without the engine too
+pub mod tile {
+    use crate::game::amino::AminoType;
+
+    pub type Tile = ((u32, u32), (u32, u32));
+
+    pub const CONNECTOR_H: Tile = ((0, 24), (2, 2));
+    pub const CONNECTOR_V: Tile = ((3, 24), (2, 2));
+    pub const INTERACTION_H: Tile = ((0, 27), (2, 2));
+    pub const INTERACTION_V: Tile = ((3, 27), (2, 2));
+
+    pub fn amino(amino: AminoType) -> Tile {
+        let pos = match amino {
+            AminoType::Arg => (0, 0),
+            AminoType::Leu => (11, 0),
+            AminoType::Ala => (22, 0),
+            AminoType::Cys => (0, 12),
+            AminoType::Asp => (11, 12),
+            AminoType::Phe => (22, 12),
+        };
+        (pos, (10, 11))
+    }
+}
+
 pub macro include_asset($name:expr) {
     include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/assets/", $name))
 }
@@ -62,20 +89,22 @@ pub fn init(assets: &mut AssetConstructor) {
     assets.register_sprite(tiles, COLLAPSE, (9, 36), (8, 9));
     assets.register_sprite(tiles, EXPAND, (0, 36), (8, 9));
 
-    assets.register_sprite(tiles, AMINO_R, (0, 0), (10, 11));
-    assets.register_sprite(tiles, AMINO_L, (11, 0), (10, 11));
-    assets.register_sprite(tiles, AMINO_A, (22, 0), (10, 11));
-    assets.register_sprite(tiles, AMINO_C, (0, 12), (10, 11));
-    assets.register_sprite(tiles, AMINO_D, (11, 12), (10, 11));
-    assets.register_sprite(tiles, AMINO_F, (22, 12), (10, 11));
+    for amino in AminoType::ALL {
+        let (pos, size) = tile::amino(amino);
+        assets.register_sprite(tiles, amino.asset(), pos, size);
+    }
 
     assets.register_sprite(tiles, SELECTED, (22, 24), (10, 11));
     assets.register_sprite(tiles, GHOST, (11, 24), (10, 11));
 
-    assets.register_sprite(tiles, CONNECTOR_H, (0, 24), (2, 2));
-    assets.register_sprite(tiles, CONNECTOR_V, (3, 24), (2, 2));
-    assets.register_sprite(tiles, INTERACTION_H, (0, 27), (2, 2));
-    assets.register_sprite(tiles, INTERACTION_V, (3, 27), (2, 2));
+    for (sprite, (pos, size)) in [
+        (CONNECTOR_H, tile::CONNECTOR_H),
+        (CONNECTOR_V, tile::CONNECTOR_V),
+        (INTERACTION_H, tile::INTERACTION_H),
+        (INTERACTION_V, tile::INTERACTION_V),
+    ] {
+        assets.register_sprite(tiles, sprite, pos, size);
+    }
 
     load_font(
         assets,
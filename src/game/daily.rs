@@ -0,0 +1,46 @@
+use std::collections::HashMap;
+
+use chrono::{Datelike, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::{generator::GeneratorConfig, level::Level},
+    misc::save,
+};
+
+const SAVE_FILE: &str = "daily.ron";
+// as hard as the middle of a random run, which is quick enough to generate
+const DIFFICULTY: u32 = 3;
+
+#[derive(Default, Clone, Serialize, Deserialize)]
+pub struct DailyRecord {
+    pub best: Option<f32>,
+    pub attempts: u32,
+    // in seconds
+    pub time: Option<f32>,
+}
+
+pub fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+pub fn load_record(date: NaiveDate) -> DailyRecord {
+    let mut records = save::load::<HashMap<String, DailyRecord>>(SAVE_FILE);
+    records.remove(&date.to_string()).unwrap_or_default()
+}
+
+pub fn save_record(date: NaiveDate, record: &DailyRecord) {
+    let mut records = save::load::<HashMap<String, DailyRecord>>(SAVE_FILE);
+    records.insert(date.to_string(), record.clone());
+    save::save(SAVE_FILE, &records);
+}
+
+pub fn level(date: NaiveDate) -> Level {
+    let seed = date.year() as u64 * 10_000 + date.month() as u64 * 100 + date.day() as u64;
+    let config = GeneratorConfig::difficulty(DIFFICULTY);
+    // difficulty configs are always valid
+    let mut level = Level::generate_with(&config, seed).unwrap();
+    level.title = format!("Daily {date}");
+    level.description = format!("The daily challenge for {date}, check back tomorrow for another.");
+    level
+}
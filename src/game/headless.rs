@@ -0,0 +1,260 @@
+use std::{collections::HashMap, fs::File, io::BufWriter, path::Path, sync::LazyLock};
+
+use engine::exports::nalgebra::Vector2;
+use image::{
+    Delay, Frame, ImageResult, Rgba, RgbaImage,
+    codecs::gif::{GifEncoder, Repeat},
+};
+use serde::Deserialize;
+
+use crate::{
+    assets::{
+        include_asset,
+        tile::{self, Tile},
+    },
+    game::{amino::Amino, peptide::Peptide, render::CONNECTOR_OFFSETS},
+    misc::direction::{Direction, Directions},
+};
+
+static TILES: LazyLock<RgbaImage> = LazyLock::new(|| {
+    image::load_from_memory(include_asset!("tiles.png"))
+        .unwrap()
+        .to_rgba8()
+});
+
+static FONT: LazyLock<(RgbaImage, FontDescriptor)> = LazyLock::new(|| {
+    let atlas = image::load_from_memory(include_asset!("fonts/undead-pixel-11.png")).unwrap();
+    let descriptor = ron::de::from_bytes(include_asset!("fonts/undead-pixel-11.ron")).unwrap();
+    (atlas.to_rgba8(), descriptor)
+});
+
+// empty space around the peptide, in unscaled pixels
+const PADDING: f32 = 2.0;
+
+const BACKGROUND: Rgba<u8> = Rgba([0x22, 0x20, 0x34, 0xFF]);
+const TEXT_SHADOW: Rgba<u8> = Rgba([0x5c, 0x5b, 0x6a, 0xFF]);
+const FRAME_DELAY: u32 = 500;
+const LAST_FRAME_DELAY: u32 = 2_000;
+
+// the parts of the engine's font descriptor needed to draw text
+#[derive(Deserialize)]
+struct FontDescriptor {
+    characters: HashMap<char, Glyph>,
+    unknown: Glyph,
+    height: u32,
+    space_width: u32,
+    tracking: f32,
+}
+
+#[derive(Deserialize)]
+struct Glyph {
+    uv: (u32, u32),
+    size: (u32, u32),
+    #[serde(default)]
+    baseline_shift: i32,
+}
+
+pub trait RenderImage {
+    // at `scale` image pixels per sprite pixel, on a transparent background
+    fn render_image(&self, interactions: bool, scale: u32) -> RgbaImage;
+
+    fn save_png(&self, path: impl AsRef<Path>, interactions: bool, scale: u32) -> ImageResult<()>;
+
+    // one residue per frame in tree order, with its energy written above it
+    fn render_build(&self, scale: u32) -> Vec<RgbaImage>;
+
+    fn save_gif(&self, path: impl AsRef<Path>, scale: u32) -> ImageResult<()>;
+}
+
+impl RenderImage for Peptide {
+    fn render_image(&self, interactions: bool, scale: u32) -> RgbaImage {
+        if self.inner.is_empty() {
+            return RgbaImage::new(1, 1);
+        }
+
+        let bounds = self.bounds();
+        let size = canvas_size(bounds, scale);
+        let mut image = RgbaImage::new(size.x, size.y);
+        draw_image(self, &mut image, bounds, 0, interactions, scale);
+        image
+    }
+
+    fn save_png(&self, path: impl AsRef<Path>, interactions: bool, scale: u32) -> ImageResult<()> {
+        self.render_image(interactions, scale).save(path)
+    }
+
+    fn render_build(&self, scale: u32) -> Vec<RgbaImage> {
+        if self.inner.is_empty() {
+            return Vec::new();
+        }
+
+        let bounds = self.bounds();
+        let size = canvas_size(bounds, scale);
+        let header = (FONT.1.height + 4) * scale;
+
+        let mut frames = Vec::new();
+        let mut partial = Peptide::empty();
+        for pos in self.tree_order() {
+            let amino = Amino {
+                amino: self.get(pos).unwrap().amino,
+                children: Directions::empty(),
+            };
+            partial.inner.insert(pos, amino);
+            if let Some((parent, dir)) = self.parent(pos) {
+                let parent = partial.inner.get_mut(&parent).unwrap();
+                parent.children.set(dir.opposite());
+            }
+
+            let mut image = RgbaImage::from_pixel(size.x, size.y + header, BACKGROUND);
+            draw_image(&partial, &mut image, bounds, header as i64, true, scale);
+
+            let text = format!("Energy: {:.2}", partial.score());
+            let corner = Vector2::repeat(PADDING as i64 * scale as i64);
+            draw_text(&mut image, &text, corner, scale);
+            frames.push(image);
+        }
+
+        frames
+    }
+
+    fn save_gif(&self, path: impl AsRef<Path>, scale: u32) -> ImageResult<()> {
+        let frames = self.render_build(scale);
+        let count = frames.len();
+
+        let mut encoder = GifEncoder::new(BufWriter::new(File::create(path)?));
+        encoder.set_repeat(Repeat::Infinite)?;
+        encoder.encode_frames(frames.into_iter().enumerate().map(|(i, image)| {
+            let delay = if i + 1 == count {
+                LAST_FRAME_DELAY
+            } else {
+                FRAME_DELAY
+            };
+            Frame::from_parts(image, 0, 0, Delay::from_numer_denom_ms(delay, 1))
+        }))
+    }
+}
+
+// draws the peptide into the part of the image below `top` pixels, laid
+// out to fit `bounds`
+fn draw_image(
+    peptide: &Peptide,
+    image: &mut RgbaImage,
+    bounds: (Vector2<i32>, Vector2<i32>),
+    top: i64,
+    interactions: bool,
+    scale: u32,
+) {
+    // top left corner of the image, with y going up like on screen
+    let origin = Vector2::new(
+        bounds.0.x as f32 * 12.0 - 5.0 - PADDING,
+        bounds.1.y as f32 * 12.0 + 5.5 + PADDING,
+    );
+    let mut draw = |tile: Tile, center: Vector2<f32>| {
+        let size = Vector2::new(tile.1.0, tile.1.1).map(|x| x as f32);
+        let corner = center + Vector2::new(-size.x, size.y) / 2.0;
+        let corner = Vector2::new(corner.x - origin.x, origin.y - corner.y) * scale as f32;
+        let corner = corner.map(|x| x.round() as i64) + Vector2::y() * top;
+        blit(image, &TILES, tile, corner, scale, None);
+    };
+
+    for (pos, amino) in &peptide.inner {
+        draw(tile::amino(amino.amino), pos.map(|x| x as f32 * 12.0));
+    }
+
+    for (pos, amino) in &peptide.inner {
+        let center = pos.map(|x| x as f32 * 12.0);
+        for dir in amino.children.iter() {
+            let connector = [tile::CONNECTOR_V, tile::CONNECTOR_H][dir.horizontal() as usize];
+            draw(connector, center + CONNECTOR_OFFSETS[dir as usize]);
+        }
+    }
+
+    if !interactions {
+        return;
+    }
+
+    for (a, b, _) in peptide.interactions() {
+        let dir = Direction::from_delta(b - a).unwrap();
+        let marker = [tile::INTERACTION_V, tile::INTERACTION_H][dir.horizontal() as usize];
+        draw(
+            marker,
+            a.map(|x| x as f32 * 12.0) + CONNECTOR_OFFSETS[dir as usize],
+        );
+    }
+}
+
+fn canvas_size(bounds: (Vector2<i32>, Vector2<i32>), scale: u32) -> Vector2<u32> {
+    let size = (bounds.1 - bounds.0).map(|x| x as f32 * 12.0) + Vector2::new(10.0, 11.0);
+    ((size + Vector2::repeat(PADDING * 2.0)) * scale as f32).map(|x| x as u32)
+}
+
+// writes a line of text with its top left corner at `corner`, with the same
+// drop shadow as in game
+fn draw_text(image: &mut RgbaImage, text: &str, corner: Vector2<i64>, scale: u32) {
+    let (atlas, font) = &*FONT;
+    for (offset, tint) in [(scale as i64, Some(TEXT_SHADOW)), (0, None)] {
+        let mut x = 0.0;
+        for chr in text.chars() {
+            if chr == ' ' {
+                x += font.space_width as f32 + font.tracking;
+                continue;
+            }
+
+            let glyph = font.characters.get(&chr).unwrap_or(&font.unknown);
+            let top = font.height as i64 - glyph.size.1 as i64 - glyph.baseline_shift as i64;
+            let pos = Vector2::new(x.round() as i64, top) * scale as i64;
+            let pos = corner + pos + Vector2::y() * offset;
+            blit(image, atlas, (glyph.uv, glyph.size), pos, scale, tint);
+
+            x += glyph.size.0 as f32 + font.tracking;
+        }
+    }
+}
+
+// draws part of an atlas scaled up with nearest neighbor sampling,
+// optionally replacing its color
+fn blit(
+    image: &mut RgbaImage,
+    atlas: &RgbaImage,
+    tile: Tile,
+    corner: Vector2<i64>,
+    scale: u32,
+    tint: Option<Rgba<u8>>,
+) {
+    let ((x, y), (width, height)) = tile;
+    for dy in 0..height * scale {
+        for dx in 0..width * scale {
+            let (px, py) = (corner.x + dx as i64, corner.y + dy as i64);
+            if px < 0 || py < 0 || px >= image.width() as i64 || py >= image.height() as i64 {
+                continue;
+            }
+
+            let mut color = *atlas.get_pixel(x + dx / scale, y + dy / scale);
+            if let Some(tint) = tint {
+                color = Rgba([tint[0], tint[1], tint[2], color[3]]);
+            }
+
+            let pixel = image.get_pixel_mut(px as u32, py as u32);
+            *pixel = over(color, *pixel);
+        }
+    }
+}
+
+fn over(top: Rgba<u8>, bottom: Rgba<u8>) -> Rgba<u8> {
+    let (ta, ba) = (top[3] as f32 / 255.0, bottom[3] as f32 / 255.0);
+    let alpha = ta + ba * (1.0 - ta);
+    if alpha == 0.0 {
+        return Rgba([0; 4]);
+    }
+
+    let channel = |i: usize| {
+        let color = top[i] as f32 * ta + bottom[i] as f32 * ba * (1.0 - ta);
+        (color / alpha).round() as u8
+    };
+    Rgba([
+        channel(0),
+        channel(1),
+        channel(2),
+        (alpha * 255.0).round() as u8,
+    ])
+}
@@ -0,0 +1,121 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
+
+use serde::Deserialize;
+
+use crate::{
+    game::{
+        error::LevelError,
+        level::{LEVELS, Level, MANIFEST},
+    },
+    misc::save,
+};
+
+pub static PACKS: LazyLock<Vec<Pack>> = LazyLock::new(|| {
+    let mut packs = vec![Pack {
+        title: "Campaign".into(),
+        author: "Connor Slade".into(),
+        levels: LEVELS.clone(),
+    }];
+
+    let user = save::data_dir().map(|x| x.join("levels"));
+    let args = (env::args().skip(1))
+        .filter(|x| !x.starts_with("--"))
+        .map(PathBuf::from);
+    for dir in user.into_iter().chain(args) {
+        packs.extend(Pack::scan(&dir));
+    }
+
+    packs
+});
+
+pub struct Pack {
+    pub title: String,
+    pub author: String,
+    pub levels: Vec<Result<Level, LevelError>>,
+}
+
+#[derive(Deserialize)]
+struct Manifest {
+    title: String,
+    author: String,
+    // files not listed are played after them in alphabetical order
+    #[serde(default)]
+    order: Vec<String>,
+}
+
+impl Pack {
+    pub fn scan(dir: &Path) -> Vec<Pack> {
+        let mut out = Vec::new();
+        out.extend(Pack::load(dir));
+
+        let Ok(entries) = fs::read_dir(dir) else {
+            return out;
+        };
+
+        let mut dirs = (entries.filter_map(Result::ok))
+            .map(|x| x.path())
+            .filter(|x| x.is_dir())
+            .collect::<Vec<_>>();
+        dirs.sort();
+        out.extend(dirs.iter().filter_map(|x| Pack::load(x)));
+
+        out
+    }
+
+    // none if the directory has no levels
+    pub fn load(dir: &Path) -> Option<Pack> {
+        let manifest = (fs::read(dir.join(MANIFEST)).ok()).map(|x| {
+            ron::de::from_bytes::<Manifest>(&x).map_err(|x| LevelError::syntax(MANIFEST, x))
+        });
+        let (manifest, manifest_error) = match manifest {
+            Some(Ok(manifest)) => (Some(manifest), None),
+            Some(Err(error)) => (None, Some(error)),
+            None => (None, None),
+        };
+
+        let mut files = (fs::read_dir(dir).ok()?.filter_map(Result::ok))
+            .map(|x| x.path())
+            .filter(|x| x.extension().is_some_and(|x| x == "ron"))
+            .filter(|x| x.file_name().is_some_and(|x| x != MANIFEST))
+            .collect::<Vec<_>>();
+        files.sort();
+
+        if let Some(manifest) = &manifest {
+            let position = |path: &PathBuf| {
+                let name = path.file_name().unwrap().to_string_lossy();
+                (manifest.order.iter().position(|x| *x == name)).unwrap_or(usize::MAX)
+            };
+            files.sort_by_key(position);
+        }
+
+        let mut levels = (files.iter())
+            .filter_map(|x| Some((x.file_name()?.to_string_lossy(), fs::read(x).ok()?)))
+            .map(|(file, raw)| Level::load(&file, &raw))
+            .collect::<Vec<_>>();
+        if levels.is_empty() {
+            return None;
+        }
+
+        // a broken manifest shows up as an extra first level explaining what's
+        // wrong, and the levels are played in directory order
+        if let Some(error) = manifest_error {
+            levels.insert(0, Err(error));
+        }
+
+        let name = dir.file_name().unwrap_or_default().to_string_lossy();
+        let (title, author) = match manifest {
+            Some(manifest) => (manifest.title, manifest.author),
+            None => (name.into_owned(), "Unknown".into()),
+        };
+
+        Some(Pack {
+            title,
+            author,
+            levels,
+        })
+    }
+}
@@ -0,0 +1,125 @@
+use engine::{
+    assets::SpriteRef,
+    color::Rgb,
+    drawable::{Anchor, Drawable, sprite::Sprite},
+    exports::nalgebra::Vector2,
+    graphics_context::GraphicsContext,
+};
+
+use crate::{
+    assets::{self, CONNECTOR_H, CONNECTOR_V, INTERACTION_H, INTERACTION_V},
+    game::{amino::AminoType, level::Level, peptide::Peptide, world_to_screen},
+    misc::direction::Direction,
+};
+
+pub(super) const CONNECTOR_OFFSETS: [Vector2<f32>; 4] = [
+    Vector2::new(0.0, 6.5),
+    Vector2::new(0.0, -5.5),
+    Vector2::new(-6.0, 0.0),
+    Vector2::new(6.0, 0.0),
+];
+
+pub trait AminoSprite {
+    fn asset(&self) -> SpriteRef;
+}
+
+pub trait RenderPeptide {
+    fn render(
+        &self,
+        ctx: &mut GraphicsContext,
+        origin: Vector2<f32>,
+        interactions: bool,
+        callback: impl Fn(&Vector2<i32>, Sprite) -> Sprite,
+    ) -> Option<Vector2<i32>>;
+
+    fn offset_goal(&self) -> Vector2<f32>;
+}
+
+pub trait RenderLevel {
+    fn render(&self, ctx: &mut GraphicsContext, peptide: &Peptide) -> Vector2<f32>;
+}
+
+impl AminoSprite for AminoType {
+    fn asset(&self) -> SpriteRef {
+        match self {
+            AminoType::Ala => assets::AMINO_A,
+            AminoType::Cys => assets::AMINO_C,
+            AminoType::Asp => assets::AMINO_D,
+            AminoType::Phe => assets::AMINO_F,
+            AminoType::Leu => assets::AMINO_L,
+            AminoType::Arg => assets::AMINO_R,
+        }
+    }
+}
+
+impl RenderPeptide for Peptide {
+    fn render(
+        &self,
+        ctx: &mut GraphicsContext,
+        origin: Vector2<f32>,
+        interactions: bool,
+        callback: impl Fn(&Vector2<i32>, Sprite) -> Sprite,
+    ) -> Option<Vector2<i32>> {
+        let mut hover = None;
+        for (pos, amino) in self.inner.iter() {
+            let render_pos = world_to_screen(*pos);
+            let sprite = Sprite::new(amino.amino.asset())
+                .scale(Vector2::repeat(6.0))
+                .position(origin + render_pos, Anchor::Center);
+            let sprite = callback(pos, sprite);
+            sprite.is_hovered(ctx).then(|| hover = Some(*pos));
+            sprite.draw(ctx);
+
+            for dir in amino.children.iter() {
+                let connector_offset = CONNECTOR_OFFSETS[dir as usize] * 6.0;
+                Sprite::new([CONNECTOR_V, CONNECTOR_H][dir.horizontal() as usize])
+                    .scale(Vector2::repeat(6.0))
+                    .position(origin + render_pos + connector_offset, Anchor::Center)
+                    .z_index(2)
+                    .draw(ctx);
+            }
+        }
+
+        if !interactions {
+            return hover;
+        }
+
+        for (a, b, _) in self.interactions() {
+            let dir = Direction::from_delta(b - a).unwrap();
+            let render_pos = world_to_screen(a) + CONNECTOR_OFFSETS[dir as usize] * 6.0;
+            Sprite::new([INTERACTION_V, INTERACTION_H][dir.horizontal() as usize])
+                .scale(Vector2::repeat(6.0))
+                .position(origin + render_pos, Anchor::Center)
+                .z_index(2)
+                .draw(ctx);
+        }
+
+        hover
+    }
+
+    fn offset_goal(&self) -> Vector2<f32> {
+        let (min, max) = self.bounds();
+        let center = (min.map(|c| c as f32) + max.map(|c| c as f32)) / 2.0;
+        let offset = center * 12.0 * 6.0;
+
+        -Vector2::new(offset.x, offset.y)
+    }
+}
+
+impl RenderLevel for Level {
+    fn render(&self, ctx: &mut GraphicsContext, peptide: &Peptide) -> Vector2<f32> {
+        let bounds = self.peptide.bounds();
+        let width = (bounds.1.x - bounds.0.x) as f32 * 12.0 * 6.0;
+        let pos = Vector2::new(ctx.center().x - width / 2.0, 48.0)
+            - bounds.0.map(|x| x as f32) * 12.0 * 6.0;
+        self.peptide.render(ctx, pos, false, |pos, sprite| {
+            let path = self.peptide.path(*pos);
+            if peptide.find(&path).is_some() {
+                sprite.color(Rgb::hex(0x222034).lerp(Rgb::repeat(1.0), 0.6))
+            } else {
+                sprite
+            }
+        });
+        pos
+    }
+}
@@ -1,6 +1,6 @@
 #![feature(decl_macro)]
 
-use std::mem;
+use std::{env, fs, mem, process};
 
 use engine::{
     application::{Application, ApplicationArgs},
@@ -9,6 +9,7 @@ use engine::{
 
 use crate::{
     consts::{LEVEL_STATUS, SCREEN, colors},
+    game::{headless::RenderImage, level, peptide::Peptide},
     screens::{
         Screen,
         game::{GameScreen, LevelStatus},
@@ -23,6 +24,51 @@ mod misc;
 mod screens;
 
 fn main() {
+    if env::args().any(|x| x == "--verify-levels") {
+        let problems = level::verify_levels();
+        problems.iter().for_each(|x| eprintln!("{x}"));
+        process::exit(!problems.is_empty() as i32);
+    }
+
+    const EXPORTS: [&str; 6] = [
+        "--export-png",
+        "--export-gif",
+        "--export-pdb",
+        "--export-metrics",
+        "--export-contacts",
+        "--export-residues",
+    ];
+    for flag in EXPORTS {
+        let Some(idx) = env::args().position(|x| x == flag) else {
+            continue;
+        };
+
+        let args = env::args().skip(idx + 1).collect::<Vec<_>>();
+        let [fold, path, ..] = &args[..] else {
+            eprintln!("usage: {flag} <fold> <path>");
+            process::exit(1);
+        };
+
+        let result = Peptide::from_fold(fold)
+            .map_err(|x| x.to_string())
+            .and_then(|x| match flag {
+                "--export-png" => x.save_png(path, true, 6).map_err(|x| x.to_string()),
+                "--export-gif" => x.save_gif(path, 6).map_err(|x| x.to_string()),
+                "--export-pdb" => fs::write(path, x.to_pdb()).map_err(|x| x.to_string()),
+                "--export-metrics" => {
+                    fs::write(path, x.metrics().to_json()).map_err(|x| x.to_string())
+                }
+                "--export-contacts" => {
+                    fs::write(path, x.metrics().contacts_csv()).map_err(|x| x.to_string())
+                }
+                _ => fs::write(path, x.metrics().residues_csv()).map_err(|x| x.to_string()),
+            });
+        if let Err(error) = &result {
+            eprintln!("{error}");
+        }
+        process::exit(result.is_err() as i32);
+    }
+
     Application::new(ApplicationArgs {
         window_attributes: WindowAttributes::default().with_title("Peptide Synth"),
         asset_constructor: Box::new(assets::init),
@@ -35,9 +81,12 @@ fn main() {
                 ctx.background(colors::BACKGROUND);
                 if let Some(status) = ctx.memory.get_mut::<Option<LevelStatus>>(LEVEL_STATUS) {
                     match mem::take(status) {
-                        Some(LevelStatus::Campaign { .. }) => game = GameScreen::new(),
+                        Some(LevelStatus::Campaign { pack, .. }) => {
+                            game = GameScreen::campaign(pack)
+                        }
                         Some(LevelStatus::Random { .. }) => game.randomize(),
-                        None => {}
+                        Some(LevelStatus::Daily { .. }) => game.daily(),
+                        Some(LevelStatus::Shared { .. }) | None => {}
                     }
                 }
 
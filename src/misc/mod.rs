@@ -1,5 +1,7 @@
+pub use peptide_core::direction;
+
 pub mod button;
-pub mod direction;
+pub mod save;
 
 pub fn lerp(start: f32, end: f32, t: f32) -> f32 {
     let lerp = start + (end - start) * t;
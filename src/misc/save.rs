@@ -0,0 +1,39 @@
+use std::{env, fs, path::PathBuf};
+
+use ron::ser::PrettyConfig;
+use serde::{Serialize, de::DeserializeOwned};
+
+pub fn data_dir() -> Option<PathBuf> {
+    let base = if cfg!(windows) {
+        PathBuf::from(env::var_os("APPDATA")?)
+    } else if cfg!(target_os = "macos") {
+        PathBuf::from(env::var_os("HOME")?).join("Library/Application Support")
+    } else if let Some(data) = env::var_os("XDG_DATA_HOME") {
+        PathBuf::from(data)
+    } else {
+        PathBuf::from(env::var_os("HOME")?).join(".local/share")
+    };
+
+    Some(base.join("peptide-synth"))
+}
+
+// falls back to the default if the file is missing or corrupt
+pub fn load<T: DeserializeOwned + Default>(name: &str) -> T {
+    (data_dir())
+        .and_then(|dir| fs::read(dir.join(name)).ok())
+        .and_then(|raw| ron::de::from_bytes(&raw).ok())
+        .unwrap_or_default()
+}
+
+pub fn save<T: Serialize>(name: &str, value: &T) {
+    let Some(dir) = data_dir() else {
+        return;
+    };
+
+    let Ok(raw) = ron::ser::to_string_pretty(value, PrettyConfig::default()) else {
+        return;
+    };
+
+    let _ = fs::create_dir_all(&dir);
+    let _ = fs::write(dir.join(name), raw);
+}
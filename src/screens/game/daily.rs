@@ -0,0 +1,64 @@
+use std::thread;
+
+use crate::{
+    game::daily::{self, DailyRecord},
+    screens::game::{GameScreen, LevelStatus},
+};
+
+impl GameScreen {
+    pub fn daily(&mut self) {
+        let date = daily::today();
+        let mut record = daily::load_record(date);
+        record.attempts += 1;
+        daily::save_record(date, &record);
+
+        self.level_status = LevelStatus::Daily {
+            date,
+            solved: false,
+            elapsed: 0.0,
+            record,
+            generator: Some(thread::spawn(move || daily::level(date))),
+        };
+    }
+
+    pub fn update_daily(&mut self, energy: f32, won: bool) {
+        let LevelStatus::Daily {
+            date,
+            solved,
+            elapsed,
+            record,
+            ..
+        } = &mut self.level_status
+        else {
+            return;
+        };
+
+        let mut changed = false;
+        if record.best.is_none_or(|best| energy < best) {
+            record.best = Some(energy);
+            changed = true;
+        }
+
+        if won && !*solved {
+            *solved = true;
+            record.time = Some(record.time.map_or(*elapsed, |time| time.min(*elapsed)));
+            changed = true;
+        }
+
+        changed.then(|| daily::save_record(*date, record));
+    }
+}
+
+pub fn describe(record: &DailyRecord) -> String {
+    let mut out = format!("\nAttempts: {}", record.attempts);
+    if let Some(best) = record.best {
+        out.push_str(&format!("\nBest energy: {best:.2}"));
+    }
+
+    if let Some(time) = record.time {
+        let time = time as u32;
+        out.push_str(&format!("\nBest time: {}:{:02}", time / 60, time % 60));
+    }
+
+    out
+}
@@ -19,11 +19,11 @@ use engine::{
 use crate::{
     assets::{COLLAPSE, EX, EXPAND, LEFT_ARROW, RIGHT_ARROW, SCORE_ARROW, SCORE_BAR, UNDEAD_FONT},
     consts::SCREEN,
-    game::{amino::AminoType, level::LEVELS},
+    game::{amino::AminoType, level::WIN_THRESHOLD, pack::PACKS, render::AminoSprite},
     misc::{button::ButtonExt, exp_decay},
     screens::{
         Screen,
-        game::{GameScreen, LevelStatus},
+        game::{GameScreen, LevelStatus, daily},
     },
 };
 
@@ -67,6 +67,7 @@ impl GameScreen {
 
                                 match self.level_status {
                                     LevelStatus::Campaign {
+                                        pack,
                                         level_idx,
                                         unlocked,
                                     } => {
@@ -79,10 +80,12 @@ impl GameScreen {
 
                                         arrow(RIGHT_ARROW, right_color)
                                             .on_click(ctx, || {
-                                                if level_idx + 1 == LEVELS.len() {
+                                                if level_idx + 1 < PACKS[pack].levels.len() {
+                                                    self.load_level(level_idx + 1)
+                                                } else if pack == 0 {
                                                     win = true;
                                                 } else {
-                                                    self.load_level(level_idx + 1)
+                                                    close = true;
                                                 }
                                             })
                                             .layout(ctx, layout);
@@ -110,6 +113,7 @@ impl GameScreen {
                                             })
                                             .layout(ctx, layout);
                                     }
+                                    LevelStatus::Daily { .. } | LevelStatus::Shared { .. } => {}
                                 }
 
                                 Spacer::new_x(layout.available().x)
@@ -123,6 +127,18 @@ impl GameScreen {
                     let mut desc = self.level.description.clone();
                     if let LevelStatus::Random { count, .. } = self.level_status {
                         desc.push_str(&format!("\nSolved: {count}"));
+                        match &self.seed_input {
+                            Some(input) => desc.push_str(&format!("\nSeed: {input}_")),
+                            None => desc.push_str("\nType a seed to play it."),
+                        }
+                    }
+
+                    if let LevelStatus::Daily { record, .. } = &self.level_status {
+                        desc.push_str(&daily::describe(record));
+                    }
+
+                    if let Some(message) = &self.share_message {
+                        desc.push_str(&format!("\n{message}"));
                     }
 
                     Text::new(UNDEAD_FONT, &desc)
@@ -130,22 +146,46 @@ impl GameScreen {
                         .max_width(530.0)
                         .shadow(-Vector2::y(), Rgb::hex(0x5c5b6a))
                         .layout(ctx, layout);
+
+                    RowLayout::new(16.0).show(ctx, layout, |ctx, layout| {
+                        let button = |text, key| {
+                            Text::new(UNDEAD_FONT, text)
+                                .scale(Vector2::repeat(2.0))
+                                .shadow(-Vector2::y(), Rgb::hex(0x5c5b6a))
+                                .button(memory_key!(key))
+                        };
+
+                        button("Copy Level", 0)
+                            .on_click(ctx, || self.export_level())
+                            .layout(ctx, layout);
+                        button("Copy Solution", 1)
+                            .on_click(ctx, || self.export_solution())
+                            .layout(ctx, layout);
+                        button("Paste Code", 2)
+                            .on_click(ctx, || self.import())
+                            .layout(ctx, layout);
+                    });
                 }
 
                 let energy = self.peptide.score();
-                let range = self.level.range;
-                let score = (energy - range.1) / (range.0 - range.1);
-
-                if score >= 0.95 && self.peptide.inner.len() == self.level.peptide.inner.len() {
+                let score = self.level.score(energy);
+                let complete = self.peptide.inner.len() == self.level.peptide.inner.len();
+                if score >= WIN_THRESHOLD && complete {
                     match &mut self.level_status {
                         LevelStatus::Campaign {
                             level_idx,
                             unlocked,
+                            ..
                         } => *unlocked = (*unlocked).max(*level_idx + 1),
                         LevelStatus::Random { solved, .. } => *solved = true,
+                        LevelStatus::Daily { .. } | LevelStatus::Shared { .. } => {}
                     }
                 }
 
+                if complete {
+                    self.update_daily(energy, score >= WIN_THRESHOLD);
+                }
+
                 let offset_goal = score.clamp(0.0, 1.0) * 57.0 * 6.0;
                 let offset = ctx.memory.get_or_insert(memory_key!(), offset_goal);
                 *offset = exp_decay(*offset, offset_goal, 10.0, ctx.delta_time);
@@ -162,7 +202,7 @@ impl GameScreen {
                             .scale(Vector2::repeat(6.0))
                             .layout(ctx, layout);
 
-                        let duration = if score >= 0.95 {
+                        let duration = if score >= WIN_THRESHOLD {
                             format!("{score:.1} decade{}", if score >= 1.05 { "s" } else { "" })
                         } else {
                             format!("{:.1} years", score * 10.0 + f32::EPSILON)
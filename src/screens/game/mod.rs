@@ -4,28 +4,37 @@ use std::{
     thread::{self, JoinHandle},
 };
 
+use chrono::NaiveDate;
 use engine::{
     color::Rgb,
     drawable::{Anchor, Drawable, sprite::Sprite, text::Text},
     exports::{
         nalgebra::Vector2,
-        winit::{event::MouseButton, window::CursorIcon},
+        winit::{event::MouseButton, keyboard::KeyCode, window::CursorIcon},
     },
     graphics_context::GraphicsContext,
 };
+use rand::rng;
 
 use crate::{
     assets::{SELECTED, UNDEAD_FONT},
     game::{
-        level::{LEVELS, Level},
+        daily::DailyRecord,
+        error::playable,
+        generator::GeneratorConfig,
+        level::Level,
+        pack::PACKS,
         peptide::Peptide,
+        render::{RenderLevel, RenderPeptide},
         world_to_screen,
     },
     misc::exp_decay,
 };
 
+mod daily;
 mod interface;
 mod selection;
+mod share;
 
 pub struct GameScreen {
     peptide: Peptide,
@@ -38,11 +47,27 @@ pub struct GameScreen {
     show_desc: bool,
     child_idx: u8,
     selected: Option<Vector2<i32>>,
+    seed_input: Option<String>,
+    share_message: Option<String>,
 }
 
+const DIGITS: [KeyCode; 10] = [
+    KeyCode::Digit0,
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+];
+
 type Generator = Option<JoinHandle<Level>>;
 pub enum LevelStatus {
     Campaign {
+        pack: usize,
         level_idx: usize,
         unlocked: usize,
     },
@@ -52,16 +77,31 @@ pub enum LevelStatus {
         generator: Generator,
         next_generator: Generator,
     },
+    Daily {
+        date: NaiveDate,
+        solved: bool,
+        elapsed: f32,
+        record: DailyRecord,
+        generator: Generator,
+    },
+    Shared {
+        generator: Generator,
+    },
 }
 
 impl GameScreen {
     pub fn new() -> Self {
-        let level = &LEVELS[0];
+        Self::campaign(0)
+    }
+
+    pub fn campaign(pack: usize) -> Self {
+        let level = playable(&PACKS[pack].levels[0]);
 
         Self {
-            peptide: Peptide::for_level(level),
-            level: Cow::Borrowed(level),
+            peptide: Peptide::for_level(&level),
+            level,
             level_status: LevelStatus::Campaign {
+                pack,
                 level_idx: 0,
                 unlocked: 0,
             },
@@ -72,30 +112,41 @@ impl GameScreen {
             show_desc: true,
             child_idx: 0,
             selected: None,
+            seed_input: None,
+            share_message: None,
         }
     }
 
     pub fn randomize(&mut self) {
         if let LevelStatus::Random {
+            count,
             generator,
             next_generator,
             ..
         } = &mut self.level_status
         {
+            // the next level is generated ahead of time, so it's two levels out
             *generator = mem::take(next_generator);
-            *next_generator = Some(thread::spawn(Level::generate));
+            *next_generator = spawn_random(*count + 2);
         } else {
             self.level_status = LevelStatus::Random {
                 solved: false,
                 count: 0,
-                generator: Some(thread::spawn(Level::generate)),
-                next_generator: Some(thread::spawn(Level::generate)),
+                generator: spawn_random(0),
+                next_generator: spawn_random(1),
             };
         }
     }
 
+    pub fn load_seed(&mut self, seed: u64) {
+        if let LevelStatus::Random { generator, .. } = &mut self.level_status {
+            *generator = spawn_generator(seed);
+        }
+    }
+
     pub fn load_level(&mut self, idx: usize) {
         let LevelStatus::Campaign {
+            pack,
             level_idx,
             unlocked,
         } = &mut self.level_status
@@ -103,14 +154,13 @@ impl GameScreen {
             return;
         };
 
-        if idx >= LEVELS.len() || idx > *unlocked {
+        let levels = &PACKS[*pack].levels;
+        if idx >= levels.len() || idx > *unlocked {
             return;
         }
 
-        let level = &LEVELS[idx];
-
         *level_idx = idx;
-        self.level = Cow::Borrowed(level);
+        self.level = playable(&levels[idx]);
         self.reset();
     }
 
@@ -123,15 +173,19 @@ impl GameScreen {
     }
 
     pub fn render(&mut self, ctx: &mut GraphicsContext) {
-        if let LevelStatus::Random {
-            solved, generator, ..
-        } = &mut self.level_status
+        if let LevelStatus::Random { generator, .. }
+        | LevelStatus::Daily { generator, .. }
+        | LevelStatus::Shared { generator } = &mut self.level_status
             && let Some(handle) = generator
         {
             if handle.is_finished() {
                 let handle = mem::take(generator).unwrap();
                 self.level = Cow::Owned(handle.join().unwrap());
-                *solved = false;
+                if let LevelStatus::Random { solved, .. } | LevelStatus::Daily { solved, .. } =
+                    &mut self.level_status
+                {
+                    *solved = false;
+                }
                 self.reset();
             } else {
                 Text::new(UNDEAD_FONT, "Generating Level...")
@@ -143,8 +197,23 @@ impl GameScreen {
             }
         }
 
+        if let LevelStatus::Daily {
+            solved: false,
+            elapsed,
+            ..
+        } = &mut self.level_status
+        {
+            *elapsed += ctx.delta_time;
+        }
+
+        self.seed_input(ctx);
         self.interface(ctx);
 
+        // unavailable levels have nothing to fold
+        if self.level.peptide.inner.is_empty() {
+            return;
+        }
+
         if ctx.input.mouse_down(MouseButton::Middle) {
             ctx.window.cursor(CursorIcon::Move);
             self.pan += ctx.input.mouse_delta();
@@ -190,4 +259,61 @@ impl GameScreen {
 
         self.selection(ctx, origin, level_origin);
     }
+
+    // lets players type in the seed of a random level to play
+    fn seed_input(&mut self, ctx: &mut GraphicsContext) {
+        if !matches!(self.level_status, LevelStatus::Random { .. }) {
+            return;
+        }
+
+        for (digit, key) in DIGITS.iter().enumerate() {
+            if ctx.input.key_pressed(*key) {
+                let input = self.seed_input.get_or_insert_default();
+                input.push(char::from_digit(digit as u32, 10).unwrap());
+            }
+        }
+
+        if ctx.input.key_pressed(KeyCode::Backspace)
+            && let Some(input) = &mut self.seed_input
+        {
+            input.pop();
+        }
+
+        if ctx.input.key_pressed(KeyCode::Escape) {
+            self.seed_input = None;
+        }
+
+        if ctx.input.key_pressed(KeyCode::Enter)
+            && let Some(input) = self.seed_input.take()
+            && let Ok(seed) = input.parse()
+        {
+            self.load_seed(seed);
+        }
+    }
+}
+
+// the seed decides the difficulty, so it reproduces the level for anyone
+fn spawn_generator(seed: u64) -> Generator {
+    let config = GeneratorConfig::for_seed(seed);
+    Some(thread::spawn(move || {
+        let level = Level::generate_reporting(&config, seed, |seed, rejections| {
+            let reasons = (rejections.iter()).map(ToString::to_string);
+            let reasons = reasons.collect::<Vec<_>>();
+            eprintln!("skipped seed {seed}: {}", reasons.join(", "));
+        });
+        // difficulty configs are always valid
+        level.unwrap()
+    }))
+}
+
+fn spawn_random(solved: u32) -> Generator {
+    spawn_generator(GeneratorConfig::random_seed(solved, &mut rng()))
+}
+
+// imported levels can be big enough that solving them takes a while too
+fn spawn_solver(mut level: Level) -> Generator {
+    Some(thread::spawn(move || {
+        level.range = level.solve();
+        level
+    }))
 }
@@ -9,7 +9,7 @@ use engine::{
 
 use crate::{
     assets::{GHOST, SELECTED},
-    game::{amino::Amino, world_to_screen},
+    game::{amino::Amino, render::AminoSprite, world_to_screen},
     misc::direction::{Direction, Directions},
     screens::game::GameScreen,
 };
@@ -48,23 +48,14 @@ impl GameScreen {
             .position(level_origin + world_to_screen(level_pos), Anchor::Center)
             .draw(ctx);
 
-        let level = self.level.get(level_pos).unwrap();
-        let dirs = level.children.iter();
-        for next_dir in dirs.cycle().skip(self.child_idx as usize).take(4) {
-            let next_pos = level_pos + next_dir.delta();
-            let next = self.level.get(next_pos).unwrap();
-
-            let max = self.level.peptide.children_of_type(level_pos, next.amino);
-            let current = self.peptide.children_of_type(selected, next.amino);
-            if current >= max {
-                continue;
+        let next_pos = (self.level).next_child(&self.peptide, selected, self.child_idx);
+        match next_pos {
+            Some(next_pos) => {
+                let next = *self.level.get(next_pos).unwrap();
+                self.render_ghost(ctx, origin, level_origin, next_pos, selected, next);
             }
-
-            self.render_ghost(ctx, origin, level_origin, next_pos, selected, *next);
-            return;
+            None => self.selected = None,
         }
-
-        self.selected = None;
     }
 
     fn render_ghost(
@@ -0,0 +1,67 @@
+use arboard::Clipboard;
+
+use crate::{
+    game::{generator::GeneratorConfig, share::ShareCode},
+    screens::game::{GameScreen, LevelStatus, spawn_generator, spawn_random, spawn_solver},
+};
+
+impl GameScreen {
+    // random levels are shared as just their seed
+    pub fn export_level(&mut self) {
+        let code = match (&self.level_status, self.level.seed) {
+            (LevelStatus::Random { .. }, Some(seed)) => ShareCode::Seed { seed },
+            _ => ShareCode::Level(self.level.clone().into_owned()),
+        };
+
+        self.share_message = Some(copy(&code.encode(), "level"));
+    }
+
+    pub fn export_solution(&mut self) {
+        let code = self.level.solution_code(&self.peptide);
+        self.share_message = Some(copy(&code, "solution"));
+    }
+
+    pub fn import(&mut self) {
+        let Some(code) = Clipboard::new().and_then(|mut x| x.get_text()).ok() else {
+            self.share_message = Some("Couldn't read the clipboard.".into());
+            return;
+        };
+
+        let result = match ShareCode::decode(&code) {
+            Ok(ShareCode::Seed { seed }) => {
+                let count = GeneratorConfig::seed_difficulty(seed);
+                self.level_status = LevelStatus::Random {
+                    solved: false,
+                    count,
+                    generator: spawn_generator(seed),
+                    next_generator: spawn_random(count + 1),
+                };
+                Ok(())
+            }
+            Ok(ShareCode::Level(level)) => {
+                self.level_status = LevelStatus::Shared {
+                    generator: spawn_solver(level),
+                };
+                Ok(())
+            }
+            Ok(ShareCode::Solution { .. }) => self
+                .level
+                .decode_solution(&code)
+                .map(|peptide| self.peptide = peptide),
+            Err(error) => Err(error),
+        };
+
+        self.share_message = match result {
+            Ok(()) => None,
+            Err(error) => Some(format!("Couldn't import code: {error}.")),
+        };
+    }
+}
+
+// returns the message to show the player
+fn copy(code: &str, kind: &str) -> String {
+    match Clipboard::new().and_then(|mut x| x.set_text(code)) {
+        Ok(()) => format!("Copied {kind} code."),
+        Err(_) => format!("Couldn't copy {kind} code."),
+    }
+}
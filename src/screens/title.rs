@@ -13,7 +13,12 @@ use rand::{Rng, rng, seq::IndexedRandom};
 use crate::{
     assets::{CAMPAIGN_BUTTON, QUIT_BUTTON, RANDOM_BUTTON, UNDEAD_FONT},
     consts::{LEVEL_STATUS, SCREEN},
-    game::amino::AminoType,
+    game::{
+        amino::AminoType,
+        daily::{self, DailyRecord},
+        pack::PACKS,
+        render::AminoSprite,
+    },
     misc::button::ButtonExt,
     screens::{Screen, game::LevelStatus},
 };
@@ -73,6 +78,7 @@ impl TitleScreen {
                     .scale_effect()
                     .on_click(ctx, || {
                         game = Some(LevelStatus::Campaign {
+                            pack: 0,
                             level_idx: 0,
                             unlocked: 0,
                         })
@@ -91,6 +97,36 @@ impl TitleScreen {
                         })
                     })
                     .layout(ctx, layout);
+                Text::new(UNDEAD_FONT, "Daily")
+                    .scale(Vector2::repeat(6.0))
+                    .shadow(-Vector2::y(), Rgb::hex(0x5c5b6a))
+                    .button(memory_key!())
+                    .scale_effect()
+                    .on_click(ctx, || {
+                        game = Some(LevelStatus::Daily {
+                            date: daily::today(),
+                            solved: false,
+                            elapsed: 0.0,
+                            record: DailyRecord::default(),
+                            generator: None,
+                        })
+                    })
+                    .layout(ctx, layout);
+                for (idx, pack) in PACKS.iter().enumerate().skip(1) {
+                    Text::new(UNDEAD_FONT, format!("{} by {}", pack.title, pack.author))
+                        .scale(Vector2::repeat(3.0))
+                        .shadow(-Vector2::y(), Rgb::hex(0x5c5b6a))
+                        .button(memory_key!(idx))
+                        .scale_effect()
+                        .on_click(ctx, || {
+                            game = Some(LevelStatus::Campaign {
+                                pack: idx,
+                                level_idx: 0,
+                                unlocked: 0,
+                            })
+                        })
+                        .layout(ctx, layout);
+                }
                 Sprite::new(QUIT_BUTTON)
                     .scale(Vector2::repeat(6.0))
                     .button(memory_key!())